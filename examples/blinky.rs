@@ -22,12 +22,12 @@ fn main() -> ! {
     board_clock_init();
     let dp = pac::Peripherals::take().unwrap();
     let mut parts = dp.GLB.split();
-    let clocks = ClockConfig::new().freeze(&mut parts.clk_cfg);
+    let clocks = ClockConfig::new().freeze(&mut parts.clk_cfg).unwrap();
 
     let mut led = parts.pin17.into_pull_up_output();
 
     // Create a blocking delay function based on the current cpu frequency
-    let mut d = McycleDelay::new(clocks.sysclk().0);
+    let mut d = McycleDelay::from_clocks(clocks);
 
     loop {
         led.set_high().unwrap();
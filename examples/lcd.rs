@@ -17,7 +17,6 @@ use embedded_graphics::{
     pixelcolor::Rgb565,
     prelude::*,
 };
-use st7735_lcd;
 use st7735_lcd::Orientation;
 
 #[riscv_rt::entry]
@@ -28,7 +27,7 @@ fn main() -> ! {
     board_clock_init();
     let dp = pac::Peripherals::take().unwrap();
     let mut parts = dp.GLB.split();
-    let clocks = ClockConfig::new().freeze(&mut parts.clk_cfg);
+    let clocks = ClockConfig::new().freeze(&mut parts.clk_cfg).unwrap();
 
     let sclk = parts.pin23.into_spi_sclk();
     let mosi = parts.pin24.into_spi_mosi();
@@ -47,7 +46,7 @@ fn main() -> ! {
         clocks,
     );
 
-    let mut d = McycleDelay::new(clocks.sysclk().0);
+    let mut d = McycleDelay::from_clocks(clocks);
     cs.set_low().unwrap();
 
     let mut disp = st7735_lcd::ST7735::new(spi, dc, rst, false, true, 160, 80);
@@ -60,5 +59,7 @@ fn main() -> ! {
     let image: Image<ImageRawLE<Rgb565>> = Image::new(&image_raw, Point::new(34, 8));
     image.draw(&mut disp).unwrap();
 
-    loop {}
+    loop {
+        core::hint::spin_loop();
+    }
 }
@@ -29,7 +29,7 @@ fn main() -> ! {
     led.set_low().unwrap();
     led.set_high().unwrap();
 
-    let clocks = ClockConfig::new().freeze(&mut parts.clk_cfg);
+    let clocks = ClockConfig::new().freeze(&mut parts.clk_cfg).unwrap();
 
     // Set up uart output. Since this microcontroller has a pin matrix,
     // we need to set up both the pins and the muxs
@@ -43,16 +43,17 @@ fn main() -> ! {
         Config::default().baudrate(2_000_000.Bd()),
         ((tx, mux6), (rx, mux7)),
         clocks,
-    );
+    )
+    .unwrap();
 
     // Create a blocking delay function based on the current cpu frequency
-    let mut d = bl702_hal::delay::McycleDelay::new(bl702_hal::SYSFREQ);
+    let mut d = bl702_hal::delay::McycleDelay::from_clocks(clocks);
 
     let hello = "hello rust!\r\n";
     loop {
         d.delay_ms(100).unwrap();
         let t = serial.write_str(hello);
-        let _ = match t {
+        match t {
             Ok(_) => led.set_high().unwrap(),
             Err(_) => led.set_low().unwrap(),
         };
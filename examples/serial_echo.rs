@@ -30,7 +30,7 @@ fn main() -> ! {
     led.set_low().unwrap();
     led.set_high().unwrap();
 
-    let clocks = ClockConfig::new().freeze(&mut parts.clk_cfg);
+    let clocks = ClockConfig::new().freeze(&mut parts.clk_cfg).unwrap();
 
     // Set up uart output. Since this microcontroller has a pin matrix,
     // we need to set up both the pins and the muxs
@@ -44,7 +44,8 @@ fn main() -> ! {
         Config::default().baudrate(2_000_000.Bd()),
         ((tx, mux6), (rx, mux7)),
         clocks,
-    );
+    )
+    .unwrap();
 
     loop {
         let r = serial.read();
@@ -3,12 +3,20 @@
 use crate::{
     gpio::ClkCfg,
     system::{
+        ef_ctrl::{
+            EF_Ctrl_Get_Trim_Parity, EF_Ctrl_Read_RC32K_Trim, EF_Ctrl_Read_RC32M_Trim,
+            Efuse_Ana_RC32K_Trim_Type, Efuse_Ana_RC32M_Trim_Type,
+        },
         glb::{self, *},
         hbn::{
-            self, HBN_32K_CLK_Type, HBN_32K_Sel, HBN_Power_On_Xtal_32K, HBN_Set_XCLK_CLK_Sel,
-            HBN_XCLK_CLK_Type,
+            self, HBN_32K_CLK_Type, HBN_32K_Sel, HBN_Power_Off_Xtal_32K, HBN_Power_On_Xtal_32K,
+            HBN_Set_XCLK_CLK_Sel, HBN_Trim_RC32K, HBN_UART_CLK_Type, HBN_XCLK_CLK_Type,
+        },
+        pds::{
+            self, PDS_AUDIO_PLL_Type, PDS_Audio_PLL_Is_Set, PDS_Get_Audio_PLL_Freq,
+            PDS_PLL_XTAL_Type, PDS_Set_Audio_PLL_Freq, PDS_Trim_RC32M,
         },
-        pds,
+        BL_Err_Type,
     },
 };
 use embedded_time::rate::Hertz;
@@ -32,42 +40,268 @@ pub enum system_clock_type {
     SYSTEM_CLOCK_AUPLL,
 }
 
-/// System bus frequency
-pub const SYSFREQ: u32 = 144_000_000;
 /// External high-speed crystal frequency
 pub const XTAL_FREQ: u32 = 32_000_000;
+/// Internal high-speed RC oscillator frequency
+pub const RC32M_FREQ: u32 = 32_000_000;
 /// UART peripheral clock frequency when PLL selected
 pub const UART_PLL_FREQ: u32 = 96_000_000;
+/// Internal low-speed RC oscillator frequency
+pub const RC32K_FREQ: u32 = 32_000;
+/// External low-speed crystal frequency
+pub const XTAL32K_FREQ: u32 = 32_768;
 
+/// System (root) clock source
 #[derive(PartialEq, Eq, Copy, Clone)]
-#[repr(u32)]
 pub enum SysclkFreq {
-    Pll144Mhz = 144_000_000,
+    /// Internal 32MHz RC oscillator
+    Rc32Mhz,
+    /// External 32MHz crystal
+    Xtal32Mhz,
+    /// DLL 57.6MHz output
+    Pll57P6Mhz,
+    /// DLL 96MHz output
+    Pll96Mhz,
+    /// DLL 144MHz output
+    Pll144Mhz,
+}
+
+impl From<SysclkFreq> for GLB_SYS_CLK_Type {
+    fn from(freq: SysclkFreq) -> Self {
+        match freq {
+            SysclkFreq::Rc32Mhz => GLB_SYS_CLK_Type::GLB_SYS_CLK_RC32M,
+            SysclkFreq::Xtal32Mhz => GLB_SYS_CLK_Type::GLB_SYS_CLK_XTAL,
+            SysclkFreq::Pll57P6Mhz => GLB_SYS_CLK_Type::GLB_SYS_CLK_DLL57P6M,
+            SysclkFreq::Pll96Mhz => GLB_SYS_CLK_Type::GLB_SYS_CLK_DLL96M,
+            SysclkFreq::Pll144Mhz => GLB_SYS_CLK_Type::GLB_SYS_CLK_DLL144M,
+        }
+    }
+}
+
+/// Reference clock for the DLL
+#[derive(PartialEq, Eq, Copy, Clone)]
+pub enum DllXtal {
+    /// External 32MHz crystal
+    Xtal32Mhz,
+    /// Internal 32MHz RC oscillator, for boards without a crystal
+    Rc32Mhz,
+    /// No reference, the crystal and DLL are left off.
+    /// Only [`SysclkFreq::Rc32Mhz`] and [`UartClkSource::Fclk`] can be used.
+    None,
+}
+
+impl From<DllXtal> for GLB_DLL_XTAL_Type {
+    fn from(xtal: DllXtal) -> Self {
+        match xtal {
+            DllXtal::Xtal32Mhz => GLB_DLL_XTAL_Type::GLB_DLL_XTAL_32M,
+            DllXtal::Rc32Mhz => GLB_DLL_XTAL_Type::GLB_DLL_XTAL_RC32M,
+            DllXtal::None => GLB_DLL_XTAL_Type::GLB_DLL_XTAL_NONE,
+        }
+    }
+}
+
+impl From<DllXtal> for HBN_XCLK_CLK_Type {
+    fn from(xtal: DllXtal) -> Self {
+        match xtal {
+            DllXtal::Xtal32Mhz => HBN_XCLK_CLK_Type::HBN_XCLK_CLK_XTAL,
+            DllXtal::Rc32Mhz | DllXtal::None => HBN_XCLK_CLK_Type::HBN_XCLK_CLK_RC32M,
+        }
+    }
 }
 
+/// 32kHz clock source, used by the RTC and low power timers
+#[derive(PartialEq, Eq, Copy, Clone)]
+pub enum F32kSource {
+    /// Internal RC oscillator, ~32kHz
+    Rc32k,
+    /// External 32.768kHz crystal
+    Xtal32k,
+    /// Digital 32kHz clock, divided down from XCLK
+    Dig32k,
+}
+
+impl From<F32kSource> for HBN_32K_CLK_Type {
+    fn from(src: F32kSource) -> Self {
+        match src {
+            F32kSource::Rc32k => HBN_32K_CLK_Type::HBN_32K_RC,
+            F32kSource::Xtal32k => HBN_32K_CLK_Type::HBN_32K_XTAL,
+            F32kSource::Dig32k => HBN_32K_CLK_Type::HBN_32K_DIG,
+        }
+    }
+}
+
+/// UART peripheral clock source
+#[derive(PartialEq, Eq, Copy, Clone)]
+pub enum UartClkSource {
+    /// Core clock (FCLK)
+    Fclk,
+    /// 96MHz DLL output
+    Pll96Mhz,
+}
+
+impl From<UartClkSource> for HBN_UART_CLK_Type {
+    fn from(src: UartClkSource) -> Self {
+        match src {
+            UartClkSource::Fclk => HBN_UART_CLK_Type::HBN_UART_CLK_FCLK,
+            UartClkSource::Pll96Mhz => HBN_UART_CLK_Type::HBN_UART_CLK_96M,
+        }
+    }
+}
+
+/// Machine timer (mtime) clock source
+#[derive(PartialEq, Eq, Copy, Clone)]
+pub enum MtimerClkSource {
+    /// Peripheral bus clock (BCLK)
+    Bclk,
+    /// 32kHz clock
+    F32k,
+}
+
+impl From<MtimerClkSource> for GLB_MTIMER_CLK_Type {
+    fn from(src: MtimerClkSource) -> Self {
+        match src {
+            MtimerClkSource::Bclk => GLB_MTIMER_CLK_Type::GLB_MTIMER_CLK_BCLK,
+            MtimerClkSource::F32k => GLB_MTIMER_CLK_Type::GLB_MTIMER_CLK_32K,
+        }
+    }
+}
+
+/// Audio PLL output frequency, for I2S and other audio peripherals
+#[derive(PartialEq, Eq, Copy, Clone)]
+pub enum AudioPllFreq {
+    /// 11.2896MHz, 256 * 44.1kHz
+    Aupll11P2896Mhz,
+    /// 12.288MHz, 256 * 48kHz
+    Aupll12P288Mhz,
+    /// 22.5792MHz, 512 * 44.1kHz
+    Aupll22P5792Mhz,
+    /// 24.576MHz, 512 * 48kHz
+    Aupll24P576Mhz,
+}
+
+impl From<AudioPllFreq> for PDS_AUDIO_PLL_Type {
+    fn from(freq: AudioPllFreq) -> Self {
+        match freq {
+            AudioPllFreq::Aupll11P2896Mhz => PDS_AUDIO_PLL_Type::AUDIO_PLL_11289600_HZ,
+            AudioPllFreq::Aupll12P288Mhz => PDS_AUDIO_PLL_Type::AUDIO_PLL_12288000_HZ,
+            AudioPllFreq::Aupll22P5792Mhz => PDS_AUDIO_PLL_Type::AUDIO_PLL_22579200_HZ,
+            AudioPllFreq::Aupll24P576Mhz => PDS_AUDIO_PLL_Type::AUDIO_PLL_24576000_HZ,
+        }
+    }
+}
+
+/// Clock configuration error
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ClockError {
+    /// The UART clock divider must be between 0 and 7
+    UartDividerOutOfRange,
+    /// The machine timer divider couldn't be programmed
+    MtimerDividerOutOfRange,
+    /// The system or UART clock needs the crystal or DLL, which [`DllXtal::None`] leaves off
+    MissingDllReference,
+    /// The ROM failed to switch the system clock, e.g. the crystal or DLL didn't start
+    SystemClockFailed,
+}
+
+/// Divider applied to the 32MHz digital clock to get the digital 32kHz clock
+const DIG_32K_DIV: u16 = 1000;
+
 /// Frozen clock frequencies
 ///
-/// The existance of this value indicates that the clock configuration can no longer be changed
+/// A snapshot of the clock tree. If the clocks are changed with [`ClockConfig::reconfigure`]
+/// the returned value replaces this one, and drivers need to be re-clocked.
 #[derive(Clone, Copy)]
 pub struct Clocks {
     sysclk: Hertz,
+    hclk: Hertz,
+    bclk: Hertz,
+    xclk: Hertz,
+    f32k: Hertz,
+    aupll: Hertz,
+    mtimer_clk: Hertz,
     uart_clk: Hertz,
     spi_clk: Hertz,
 }
 
 impl Clocks {
+    /// Read the current clock frequencies back from the hardware
     pub fn new() -> Self {
+        let glb = unsafe { glb::ptr() };
+        let hbn = unsafe { hbn::ptr() };
+
+        let hclk = system_clock_get(system_clock_type::SYSTEM_CLOCK_FCLK);
+        let bclk = system_clock_get(system_clock_type::SYSTEM_CLOCK_BCLK);
+
+        let clk_cfg2 = glb.clk_cfg2.read();
+        let uart_src = if hbn.hbn_glb.read().hbn_uart_clk_sel().bit_is_set() {
+            UART_PLL_FREQ
+        } else {
+            hclk
+        };
+        let uart_clk = uart_src / (clk_cfg2.uart_clk_div().bits() as u32 + 1);
+        let spi_clk = bclk / (glb.clk_cfg3.read().spi_clk_div().bits() as u32 + 1);
+
+        let cpu_clk_cfg = glb.cpu_clk_cfg.read();
+        let mtimer_clk = if cpu_clk_cfg.cpu_rtc_en().bit_is_clear() {
+            0
+        } else {
+            let mtimer_src = if cpu_clk_cfg.cpu_rtc_sel().bit_is_set() {
+                system_clock_get(system_clock_type::SYSTEM_CLOCK_32K_CLK)
+            } else {
+                bclk
+            };
+            mtimer_src / (cpu_clk_cfg.cpu_rtc_div().bits() + 1)
+        };
+
         Clocks {
-            sysclk: Hertz(SYSFREQ),
-            uart_clk: Hertz(UART_PLL_FREQ),
-            spi_clk: Hertz(SYSFREQ / 4),
+            sysclk: Hertz(system_clock_get(system_clock_type::SYSTEM_CLOCK_ROOT_CLOCK)),
+            hclk: Hertz(hclk),
+            bclk: Hertz(bclk),
+            xclk: Hertz(system_clock_get(system_clock_type::SYSTEM_CLOCK_XCLK)),
+            f32k: Hertz(system_clock_get(system_clock_type::SYSTEM_CLOCK_32K_CLK)),
+            aupll: Hertz(system_clock_get(system_clock_type::SYSTEM_CLOCK_AUPLL)),
+            mtimer_clk: Hertz(mtimer_clk),
+            uart_clk: Hertz(uart_clk),
+            spi_clk: Hertz(spi_clk),
         }
     }
 
+    /// Root clock, before the HCLK divider
     pub fn sysclk(&self) -> Hertz {
         self.sysclk
     }
 
+    /// Core and AHB clock
+    pub const fn hclk(&self) -> Hertz {
+        self.hclk
+    }
+
+    /// Peripheral bus clock
+    pub const fn bclk(&self) -> Hertz {
+        self.bclk
+    }
+
+    /// Crystal (or RC32M) clock
+    pub const fn xclk(&self) -> Hertz {
+        self.xclk
+    }
+
+    /// 32kHz clock
+    pub const fn f32k(&self) -> Hertz {
+        self.f32k
+    }
+
+    /// Audio PLL clock
+    pub const fn aupll(&self) -> Hertz {
+        self.aupll
+    }
+
+    /// Machine timer tick rate, zero if the timer clock is disabled
+    pub const fn mtimer_clk(&self) -> Hertz {
+        self.mtimer_clk
+    }
+
     pub const fn uart_clk(&self) -> Hertz {
         self.uart_clk
     }
@@ -85,6 +319,15 @@ impl Default for Clocks {
 
 pub struct ClockConfig {
     sysclk: SysclkFreq,
+    dll_xtal: DllXtal,
+    hclk_div: u8,
+    bclk_div: u8,
+    f32k: F32kSource,
+    mtimer_clk_sel: MtimerClkSource,
+    mtimer_freq: Hertz,
+    uart_clk_sel: UartClkSource,
+    uart_clk_div: u8,
+    aupll: Option<AudioPllFreq>,
 }
 
 impl ClockConfig {
@@ -92,28 +335,184 @@ impl ClockConfig {
     pub fn new() -> Self {
         ClockConfig {
             sysclk: SysclkFreq::Pll144Mhz,
+            dll_xtal: DllXtal::Xtal32Mhz,
+            hclk_div: BSP_FCLK_DIV,
+            bclk_div: BSP_BCLK_DIV,
+            f32k: F32kSource::Xtal32k,
+            mtimer_clk_sel: MtimerClkSource::Bclk,
+            mtimer_freq: Hertz(1_000_000),
+            uart_clk_sel: UartClkSource::Pll96Mhz,
+            uart_clk_div: 0,
+            aupll: None,
         }
     }
 
+    /// Select the system clock source
+    pub fn sysclk(mut self, sysclk: SysclkFreq) -> Self {
+        self.sysclk = sysclk;
+        self
+    }
+
+    /// Select the reference clock the DLL is locked to,
+    /// [`DllXtal::None`] keeps the crystal and DLL powered off
+    pub fn dll_xtal(mut self, dll_xtal: DllXtal) -> Self {
+        self.dll_xtal = dll_xtal;
+        self
+    }
+
+    /// Set the HCLK divider, HCLK = sysclk / (div + 1)
+    pub fn hclk_div(mut self, div: u8) -> Self {
+        self.hclk_div = div;
+        self
+    }
+
+    /// Set the BCLK divider, BCLK = HCLK / (div + 1)
+    pub fn bclk_div(mut self, div: u8) -> Self {
+        self.bclk_div = div;
+        self
+    }
+
+    /// Select the 32kHz clock source
+    pub fn f32k(mut self, f32k: F32kSource) -> Self {
+        self.f32k = f32k;
+        self
+    }
+
+    /// Set the machine timer clock source and tick rate
+    pub fn mtimer_clk(mut self, src: MtimerClkSource, freq: impl Into<Hertz>) -> Self {
+        self.mtimer_clk_sel = src;
+        self.mtimer_freq = freq.into();
+        self
+    }
+
+    /// Set the UART clock source and divider, uart_clk = source / (div + 1)
+    ///
+    /// `div` must be between 0 and 7, see [`ClockConfig::validate`].
+    pub fn uart_clk(mut self, src: UartClkSource, div: u8) -> Self {
+        self.uart_clk_sel = src;
+        self.uart_clk_div = div;
+        self
+    }
+
+    /// Configure and power on the audio PLL.
+    /// If this is not called the audio PLL is left as it is.
+    pub fn aupll(mut self, freq: AudioPllFreq) -> Self {
+        self.aupll = Some(freq);
+        self
+    }
+
+    /// Check for settings the hardware can't do
+    pub fn validate(&self) -> Result<(), ClockError> {
+        if self.uart_clk_div > 7 {
+            return Err(ClockError::UartDividerOutOfRange);
+        }
+        if self.dll_xtal == DllXtal::None
+            && (self.sysclk != SysclkFreq::Rc32Mhz || self.uart_clk_sel == UartClkSource::Pll96Mhz)
+        {
+            return Err(ClockError::MissingDllReference);
+        }
+        Ok(())
+    }
+
+    /// Switch the clock tree to this configuration at runtime.
+    ///
+    /// Drivers with clock-derived dividers must be quiescent while this runs: flush them first,
+    /// then pass the returned `Clocks` to their `reclock` method (e.g. [`Serial::reclock`],
+    /// [`Tx::reclock`], [`Spi::reclock`]).
+    ///
+    /// The XTAL32K and audio PLL are started before interrupts are disabled. Switching the
+    /// root clock runs with interrupts disabled, and when that has to power on the main crystal
+    /// or relock the DLL, the ROM waits for them to settle, which can take milliseconds.
+    ///
+    /// [`Serial::reclock`]: crate::uart::Serial::reclock
+    /// [`Tx::reclock`]: crate::uart::Tx::reclock
+    /// [`Spi::reclock`]: crate::spi::Spi::reclock
+    pub fn reconfigure(self, clk_cfg: &mut ClkCfg) -> Result<Clocks, ClockError> {
+        self.validate()?;
+        // Starting the XTAL32K and audio PLL is slow, do it before masking interrupts
+        self.power_on_sources();
+        riscv::interrupt::free(|| self.switch_clocks(clk_cfg))
+    }
+
     /// Calculate and balance clock registers to configure into the given clock value.
     /// Will choose closest valid value if it can't accurately select a frequency
-    pub fn freeze(self, _clk_cfg: &mut ClkCfg) -> Clocks {
-        let pll_enabled = true;
-        let sysclk = self.sysclk;
-        let uart_clk_div = 1; // leave uart clock at 96mhz
-        let spi_clk_div = 4;
+    pub fn freeze(self, clk_cfg: &mut ClkCfg) -> Result<Clocks, ClockError> {
+        self.validate()?;
+        self.power_on_sources();
+        self.switch_clocks(clk_cfg)
+    }
 
-        unsafe { hbn::ptr() }
-            .hbn_glb
-            .modify(|_, w| w.hbn_uart_clk_sel().bit(pll_enabled));
+    /// Start the XTAL32K and audio PLL if this configuration uses them,
+    /// leaving them alone if they are already running as requested
+    fn power_on_sources(&self) {
+        if self.f32k == F32kSource::Xtal32k
+            && unsafe { hbn::ptr() }
+                .xtal32k
+                .read()
+                .pu_xtal32k()
+                .bit_is_clear()
+        {
+            HBN_Power_On_Xtal_32K();
+        }
 
-        // Write UART clock divider
-        unsafe { glb::ptr() }.clk_cfg2.modify(|_, w| unsafe {
-            w.uart_clk_div()
-                .bits(uart_clk_div - 1_u8)
-                .uart_clk_en()
-                .set_bit()
-        });
+        if let Some(aupll) = self.aupll {
+            let xtal = match self.dll_xtal {
+                DllXtal::Xtal32Mhz => PDS_PLL_XTAL_Type::PDS_PLL_XTAL_32M,
+                DllXtal::Rc32Mhz | DllXtal::None => PDS_PLL_XTAL_Type::PDS_PLL_XTAL_RC32M,
+            };
+            if !PDS_Audio_PLL_Is_Set(&xtal, aupll.into()) {
+                PDS_Set_Audio_PLL_Freq(xtal, aupll.into());
+            }
+        }
+    }
+
+    /// Switch the clock muxes and dividers over, the sources must already be running
+    fn switch_clocks(self, _clk_cfg: &mut ClkCfg) -> Result<Clocks, ClockError> {
+        if !matches!(
+            GLB_Set_System_CLK(self.dll_xtal.into(), self.sysclk.into()),
+            BL_Err_Type::SUCCESS
+        ) {
+            return Err(ClockError::SystemClockFailed);
+        }
+        // XCLK feeds DIG32K and the peripherals, keep it on the same oscillator
+        HBN_Set_XCLK_CLK_Sel(self.dll_xtal.into());
+        GLB_Set_System_CLK_Div(self.hclk_div, self.bclk_div);
+
+        match self.f32k {
+            F32kSource::Xtal32k => {}
+            F32kSource::Dig32k => {
+                GLB_Set_DIG_CLK_Sel(GLB_DIG_CLK_Type::GLB_DIG_CLK_XCLK);
+                GLB_Set_DIG_32K_CLK(1, 0, DIG_32K_DIV);
+            }
+            F32kSource::Rc32k => {}
+        }
+        HBN_32K_Sel(self.f32k.into());
+        if self.f32k != F32kSource::Xtal32k {
+            HBN_Power_Off_Xtal_32K();
+        }
+
+        // The 32K clock has to be set up first, it may be the timer source
+        let mtimer_src = match self.mtimer_clk_sel {
+            MtimerClkSource::Bclk => system_clock_get(system_clock_type::SYSTEM_CLOCK_BCLK),
+            MtimerClkSource::F32k => system_clock_get(system_clock_type::SYSTEM_CLOCK_32K_CLK),
+        };
+        let mtimer_freq = self.mtimer_freq.0.max(1);
+        let mtimer_div = ((mtimer_src + mtimer_freq / 2) / mtimer_freq).clamp(1, 0x2_0000);
+        if !matches!(
+            GLB_Set_MTimer_CLK(1, self.mtimer_clk_sel.into(), mtimer_div - 1),
+            BL_Err_Type::SUCCESS
+        ) {
+            return Err(ClockError::MtimerDividerOutOfRange);
+        }
+
+        if !matches!(
+            GLB_Set_UART_CLK(1, self.uart_clk_sel.into(), self.uart_clk_div),
+            BL_Err_Type::SUCCESS
+        ) {
+            return Err(ClockError::UartDividerOutOfRange);
+        }
+
+        let spi_clk_div = 4;
 
         unsafe { glb::ptr() }.clk_cfg3.modify(|_, w| unsafe {
             w.spi_clk_div()
@@ -122,13 +521,7 @@ impl ClockConfig {
                 .set_bit()
         });
 
-        let spi_clk = system_clock_get(system_clock_type::SYSTEM_CLOCK_BCLK) / spi_clk_div as u32;
-
-        Clocks {
-            sysclk: Hertz(sysclk as u32),
-            uart_clk: Hertz(UART_PLL_FREQ),
-            spi_clk: Hertz(spi_clk),
-        }
+        Ok(Clocks::new())
     }
 }
 
@@ -138,48 +531,58 @@ impl Default for ClockConfig {
     }
 }
 
-/// This is late system init, called to reconfigure clocks as per users configuration
+/// This is late system init, called before the clocks are configured with [`ClockConfig::freeze`]
 pub fn board_clock_init() {
     system_clock_init();
     peripheral_clock_init();
 }
 
-/// Set up core clocks
+/// Set up the core clock dividers.
+/// The root clock, XCLK and 32K clock are left as the boot ROM set them,
+/// [`ClockConfig::freeze`] selects them.
 pub fn system_clock_init() {
-    // select root clock
-    GLB_Set_System_CLK(
-        GLB_DLL_XTAL_Type::GLB_DLL_XTAL_32M,
-        GLB_SYS_CLK_Type::GLB_SYS_CLK_DLL144M,
-    );
-
     // set fclk/hclk and bclk clock
     GLB_Set_System_CLK_Div(BSP_FCLK_DIV, BSP_BCLK_DIV);
     // Set MTimer the same frequency as SystemCoreClock
     GLB_Set_MTimer_CLK(
         1,
         GLB_MTIMER_CLK_Type::GLB_MTIMER_CLK_BCLK,
-        mtimer_get_clk_src_div() as u8,
+        mtimer_get_clk_src_div(),
     );
-
-    // TODO: set audio PLL
-    //  PDS_Set_Audio_PLL_Freq(BSP_AUDIO_PLL_CLOCK_SOURCE - ROOT_CLOCK_SOURCE_AUPLL_12288000_HZ);
-    HBN_Power_On_Xtal_32K();
-    HBN_32K_Sel(HBN_32K_CLK_Type::HBN_32K_XTAL);
-
-    HBN_Set_XCLK_CLK_Sel(HBN_XCLK_CLK_Type::HBN_XCLK_CLK_XTAL);
 }
 
-/// Disable all peripheral clocks, then re-enable any that we're using  
-/// TODO: move clock enable into peripheral drivers
+/// Disable all peripheral clocks.
+/// Drivers ungate their own clock when they are constructed.
 pub fn peripheral_clock_init() {
     peripheral_clock_gate_all();
-    unsafe {
-        glb::ptr().cgen_cfg1.modify(|_, w| {
-            w.uart0().set_bit();
-            w.uart1().set_bit();
-            w.spi().set_bit()
-        });
-    }
+}
+
+/// Factory RC32M trim code from eFuse, `None` if no valid trim was programmed
+pub fn rc32m_factory_trim() -> Option<u8> {
+    let mut trim = Efuse_Ana_RC32M_Trim_Type::default();
+    EF_Ctrl_Read_RC32M_Trim(&mut trim);
+    let code = trim.trimRc32mCodeFrExt();
+    let parity_ok = EF_Ctrl_Get_Trim_Parity(code as u32, 8) == trim.trimRc32mCodeFrExtParity();
+    (trim.trimRc32mExtCodeEn() && parity_ok).then_some(code)
+}
+
+/// Factory RC32K trim code from eFuse, `None` if no valid trim was programmed
+pub fn rc32k_factory_trim() -> Option<u16> {
+    let mut trim = Efuse_Ana_RC32K_Trim_Type::default();
+    EF_Ctrl_Read_RC32K_Trim(&mut trim);
+    let code = trim.trimRc32kCodeFrExt();
+    let parity_ok = EF_Ctrl_Get_Trim_Parity(code as u32, 10) == trim.trimRc32kCodeFrExtParity();
+    (trim.trimRc32kExtCodeEn() && parity_ok).then_some(code)
+}
+
+/// Apply the factory RC32M trim, returns false if there is no valid trim in eFuse
+pub fn trim_rc32m() -> bool {
+    matches!(PDS_Trim_RC32M(), BL_Err_Type::SUCCESS)
+}
+
+/// Apply the factory RC32K trim, returns false if there is no valid trim in eFuse
+pub fn trim_rc32k() -> bool {
+    matches!(HBN_Trim_RC32K(), BL_Err_Type::SUCCESS)
 }
 
 /// This is early system init - called from preinit in the C SDK
@@ -260,6 +663,10 @@ pub fn system_init() {
     //     Interrupt_Handler_Register(USB_IRQn, USB_DoNothing_IRQHandler);
     // #endif
 
+    // Apply factory oscillator trims, parts without them keep the reset values
+    trim_rc32m();
+    trim_rc32k();
+
     // HBN_BOR_CFG_Type borCfg = { 0 /* pu_bor */, 0 /* irq_bor_en */, 1 /* bor_vth */, 0 /* bor_sel */ };
     hbn.hbn_irq_mode.modify(|_r, w| {
         w.irq_bor_en().clear_bit();
@@ -292,7 +699,7 @@ fn system_clock_get(t: system_clock_type) -> u32 {
             if clksel == GLB_ROOT_CLK_Type::GLB_ROOT_CLK_RC32M
                 || clksel == GLB_ROOT_CLK_Type::GLB_ROOT_CLK_XTAL
             {
-                32_000_000
+                system_clock_get(system_clock_type::SYSTEM_CLOCK_XCLK)
             } else {
                 let pll_sel = unsafe { glb::ptr().clk_cfg0.read().reg_pll_sel().bits() };
                 match pll_sel {
@@ -312,9 +719,32 @@ fn system_clock_get(t: system_clock_type) -> u32 {
                 / (GLB_Get_HCLK_Div() as u32 + 1)
                 / (GLB_Get_BCLK_Div() as u32 + 1)
         }
-        system_clock_type::SYSTEM_CLOCK_XCLK => 32_000_000,
-        system_clock_type::SYSTEM_CLOCK_32K_CLK => 32_000,
-        // TODO: lookup!
-        system_clock_type::SYSTEM_CLOCK_AUPLL => 12_288_000,
+        system_clock_type::SYSTEM_CLOCK_XCLK => {
+            let root_clk_sel = unsafe { hbn::ptr() }
+                .hbn_glb
+                .read()
+                .hbn_root_clk_sel()
+                .bits();
+            if root_clk_sel & 1 == 0 {
+                RC32M_FREQ
+            } else {
+                XTAL_FREQ
+            }
+        }
+        system_clock_type::SYSTEM_CLOCK_32K_CLK => {
+            match unsafe { hbn::ptr() }.hbn_glb.read().hbn_f32k_sel().bits() {
+                1 => XTAL32K_FREQ,
+                3 => {
+                    let div = unsafe { glb::ptr() }
+                        .dig32k_wakeup_ctrl
+                        .read()
+                        .dig_32k_div()
+                        .bits() as u32;
+                    system_clock_get(system_clock_type::SYSTEM_CLOCK_XCLK) / div.max(1)
+                }
+                _ => RC32K_FREQ,
+            }
+        }
+        system_clock_type::SYSTEM_CLOCK_AUPLL => PDS_Get_Audio_PLL_Freq(),
     }
 }
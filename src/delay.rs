@@ -1,5 +1,6 @@
 //! Delays
 
+use crate::clock::Clocks;
 use core::convert::Infallible;
 use embedded_hal_alpha::delay::blocking::{DelayMs, DelayUs};
 
@@ -19,12 +20,17 @@ impl McycleDelay {
     /// Constructs the delay provider based on core clock frequency `freq`
     pub fn new(freq: u32) -> Self {
         Self {
-            /// System clock frequency, used to convert clock cycles
-            /// into real-world time values
+            // System clock frequency, used to convert clock cycles
+            // into real-world time values
             core_frequency: freq,
         }
     }
 
+    /// Constructs the delay provider from the frozen core clock (HCLK)
+    pub fn from_clocks(clocks: Clocks) -> Self {
+        Self::new(clocks.hclk().0)
+    }
+
     /// Retrieves the cycle count for the current HART
     #[inline]
     pub fn get_cycle_count() -> u64 {
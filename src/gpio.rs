@@ -31,9 +31,61 @@ pub trait InterruptPin {
     fn enable_interrupt(&mut self);
     fn disable_interrupt(&mut self);
     fn clear_interrupt_pending_bit(&mut self);
+    /// Returns true if this pin's interrupt status bit is set
+    fn is_pending(&self) -> bool;
+    /// Alias of [`InterruptPin::is_pending`]
     fn check_interrupt(&self) -> bool;
 }
 
+/// Bit layout of the GPIO interrupt registers, kept apart from the register access
+mod int_bits {
+    use super::Event;
+
+    /// Index of the `gpio_int_mode_setN` register holding `pin`, 0 is `gpio_int_mode_set1`
+    pub(super) const fn mode_register(pin: u8) -> usize {
+        pin as usize / 10
+    }
+
+    // 10 pins per register, each pin uses 3 bits:
+    // bit 2 is the control mode, bits 1:0 are the trigger mode
+    const fn mode_offset(pin: u8) -> u32 {
+        (pin as u32 % 10) * 3
+    }
+
+    /// Replace the trigger mode of `pin` in a `gpio_int_mode_setN` value, keeping its control mode
+    pub(super) const fn with_trigger(bits: u32, pin: u8, event: Event) -> u32 {
+        let offset = mode_offset(pin);
+        (bits & !(0b011 << offset)) | ((event as u32 & 0b11) << offset)
+    }
+
+    /// Set the control mode of `pin` in a `gpio_int_mode_setN` value
+    pub(super) const fn with_control(bits: u32, pin: u8, asynchronous: bool) -> u32 {
+        let mask = 0b100 << mode_offset(pin);
+        if asynchronous {
+            bits | mask
+        } else {
+            bits & !mask
+        }
+    }
+
+    /// Update the bit of `pin` in a `gpio_int_mask1` value, a set mask bit disables the interrupt
+    pub(super) const fn with_enabled(bits: u32, pin: u8, enable: bool) -> u32 {
+        if enable {
+            bits & !(1 << pin)
+        } else {
+            bits | (1 << pin)
+        }
+    }
+}
+
+/// Read-modify-write the `gpio_int_mode_setN` register holding `pin`
+fn modify_int_mode(pin: u8, f: impl FnOnce(u32) -> u32) {
+    let glb = unsafe { &*pac::GLB::ptr() };
+    let reg = (&glb.gpio_int_mode_set1 as *const _ as *mut u32)
+        .wrapping_add(int_bits::mode_register(pin));
+    riscv::interrupt::free(|| unsafe { reg.write_volatile(f(reg.read_volatile())) });
+}
+
 pub use uart_sig::*;
 
 /// UART signals
@@ -198,11 +250,138 @@ pub mod uart_sig {
     );
 }
 
+/// GPIO interrupt dispatcher
+///
+/// All pins share a single GPIO interrupt. Register a handler or a wake flag per pin,
+/// configure the pin with [`InterruptPin`], then call [`dispatch`](interrupt::dispatch)
+/// from the GPIO interrupt handler.
+///
+/// ```ignore
+/// let mut button = parts.pin12.into_pull_up_input();
+/// button.trigger_on_event(Event::NegativePulse);
+/// gpio::interrupt::register(12, on_button);
+/// button.enable_interrupt();
+///
+/// // in the GPIO ISR
+/// gpio::interrupt::dispatch();
+/// ```
+pub mod interrupt {
+    use core::sync::atomic::{AtomicU32, Ordering};
+
+    use crate::pac;
+
+    /// Number of GPIO pins sharing the interrupt
+    pub const PIN_COUNT: usize = 32;
+
+    /// Interrupt handler, called with the number of the pin that fired
+    pub type Handler = fn(u8);
+
+    static mut HANDLERS: [Option<Handler>; PIN_COUNT] = [None; PIN_COUNT];
+    static WAKE_FLAG_PINS: AtomicU32 = AtomicU32::new(0);
+    static WAKE_FLAGS: AtomicU32 = AtomicU32::new(0);
+
+    /// Call `handler` from [`dispatch`] when `pin` has a pending interrupt
+    pub fn register(pin: u8, handler: Handler) {
+        assert!((pin as usize) < PIN_COUNT);
+        riscv::interrupt::free(|| unsafe {
+            (*core::ptr::addr_of_mut!(HANDLERS))[pin as usize] = Some(handler);
+        });
+    }
+
+    /// Set a wake flag from [`dispatch`] when `pin` has a pending interrupt, see [`take_wake_flag`]
+    pub fn register_wake_flag(pin: u8) {
+        assert!((pin as usize) < PIN_COUNT);
+        WAKE_FLAG_PINS.fetch_or(1 << pin, Ordering::SeqCst);
+    }
+
+    /// Remove the handler and wake flag registered for `pin`
+    pub fn unregister(pin: u8) {
+        assert!((pin as usize) < PIN_COUNT);
+        riscv::interrupt::free(|| unsafe {
+            (*core::ptr::addr_of_mut!(HANDLERS))[pin as usize] = None;
+        });
+        WAKE_FLAG_PINS.fetch_and(!(1 << pin), Ordering::SeqCst);
+        WAKE_FLAGS.fetch_and(!(1 << pin), Ordering::SeqCst);
+    }
+
+    /// Returns true if the wake flag for `pin` was set since the last call, clearing it
+    pub fn take_wake_flag(pin: u8) -> bool {
+        assert!((pin as usize) < PIN_COUNT);
+        let mask = 1 << pin;
+        WAKE_FLAGS.fetch_and(!mask, Ordering::SeqCst) & mask != 0
+    }
+
+    /// Service the GPIO interrupt.
+    ///
+    /// Reads and clears the pending status of every pin, then sets wake flags and calls
+    /// handlers in priority order, lowest pin number first. Returns the pending pin mask.
+    pub fn dispatch() -> u32 {
+        let glb = unsafe { &*pac::GLB::ptr() };
+        let pending = glb.gpio_int_stat1.read().gpio_int_stat1().bits();
+        if pending == 0 {
+            return 0;
+        }
+
+        // The clear bits hold the status low while set, release them straight away
+        glb.gpio_int_clr1
+            .modify(|r, w| unsafe { w.bits(r.bits() | pending) });
+        glb.gpio_int_clr1
+            .modify(|r, w| unsafe { w.bits(r.bits() & !pending) });
+
+        WAKE_FLAGS.fetch_or(
+            pending & WAKE_FLAG_PINS.load(Ordering::SeqCst),
+            Ordering::SeqCst,
+        );
+
+        let handlers = riscv::interrupt::free(|| unsafe { *core::ptr::addr_of!(HANDLERS) });
+        for pin in 0..PIN_COUNT as u8 {
+            if pending & (1 << pin) != 0 {
+                if let Some(handler) = handlers[pin as usize] {
+                    handler(pin);
+                }
+            }
+        }
+        pending
+    }
+}
+
 /// Clock configurator registers
 pub struct ClkCfg {
     pub(crate) _ownership: (),
 }
 
+/// Clock routed to the CLK_OUT pin function
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ClockOutSource {
+    /// No clock output
+    None = 0,
+    /// I2S reference clock
+    I2sRefClk = 1,
+    /// Audio PLL output
+    AudioPll = 2,
+    /// 32MHz crystal
+    Xtal = 3,
+}
+
+impl ClkCfg {
+    /// Select the clock driven on CLK_OUT_0, available on even numbered pins.
+    ///
+    /// There is no divider in the clock output path; use the audio PLL
+    /// (see [`ClockConfig::aupll`](crate::clock::ClockConfig::aupll)) to pick a rate.
+    pub fn set_clock_out_0(&mut self, source: ClockOutSource) {
+        let glb = unsafe { &*pac::GLB::ptr() };
+        glb.clk_cfg3
+            .modify(|_, w| unsafe { w.chip_clk_out_0_sel().bits(source as u8) });
+    }
+
+    /// Select the clock driven on CLK_OUT_1, available on odd numbered pins.
+    pub fn set_clock_out_1(&mut self, source: ClockOutSource) {
+        let glb = unsafe { &*pac::GLB::ptr() };
+        glb.clk_cfg3
+            .modify(|_, w| unsafe { w.chip_clk_out_1_sel().bits(source as u8) });
+    }
+}
+
 /*
 // todo: english
     在GPIO模式下，可以设置内部上下拉，以类型状态机模式设计
@@ -216,6 +395,32 @@ pub struct Floating;
 pub struct PullDown;
 /// Pulled up pin (type state)
 pub struct PullUp;
+/// Open-drain output pin, emulated by switching the output driver on and off (type state)
+pub struct OpenDrain;
+
+/// Output driver behaviour of an output mode
+#[doc(hidden)]
+pub trait OutputMode {
+    const OPEN_DRAIN: bool = false;
+}
+
+impl OutputMode for Floating {}
+impl OutputMode for PullUp {}
+impl OutputMode for PullDown {}
+impl OutputMode for OpenDrain {
+    const OPEN_DRAIN: bool = true;
+}
+
+/// GPIO output drive strength, `Level0` is the weakest and slowest edge.
+///
+/// The BL702 has no separate slew rate control, the drive level sets both.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum DriveStrength {
+    Level0 = 0,
+    Level1 = 1,
+    Level2 = 2,
+    Level3 = 3,
+}
 
 /// Input mode (type state)
 pub struct Input<MODE> {
@@ -236,6 +441,9 @@ pub struct Spi;
 /// I2C pin mode (type state)
 pub struct I2c;
 
+/// Clock output pin mode (type state)
+pub struct ClkOut;
+
 #[doc(hidden)]
 pub trait UartPin<SIG> {}
 
@@ -244,7 +452,7 @@ pub trait UartPin<SIG> {}
 pub use self::pin::*;
 
 macro_rules! impl_glb {
-    ($($Pini: ident: ($pini: ident, $gpio_cfgctli: ident, $UartSigi: ident, $sigi: ident, $spi_kind: ident, $i2c_kind: ident, $gpio_i: ident, $gpio_int_mode_seti: ident, $pin_id: literal) ,)+) => {
+    ($($Pini: ident: ($pini: ident, $gpio_cfgctli: ident, $UartSigi: ident, $sigi: ident, $spi_kind: ident, $i2c_kind: ident, $gpio_i: ident, $pin_id: literal) ,)+) => {
         impl GlbExt for pac::GLB {
             fn split(self) -> Parts {
                 Parts {
@@ -331,6 +539,14 @@ macro_rules! impl_glb {
                     self.into_pin_with_mode(11, false, true, false)
                 }
 
+                /// Configures the pin to operate as an open-drain output pin, initially released (high).
+                /// The input buffer stays active, so the line level can be read back.
+                pub fn into_open_drain_output(self) -> $Pini<Output<OpenDrain>> {
+                    let pin: $Pini<Output<OpenDrain>> = self.into_pin_with_mode(11, false, false, true);
+                    pin.set_output_value(false);
+                    pin
+                }
+
                 /// Configures the pin to operate as a Hi-Z floating input pin.
                 pub fn into_floating_input(self) -> $Pini<Input<Floating>> {
                     self.into_pin_with_mode(11, false, false, true)
@@ -368,21 +584,61 @@ macro_rules! impl_glb {
                 }
             }
 
-            impl<MODE> $Pini<Input<MODE>> {
+            impl<MODE> $Pini<MODE> {
+                /// Erase the pin number from the type, so pins can be stored in arrays
+                pub fn degrade(self) -> AnyPin<MODE> {
+                    AnyPin { pin: $pin_id, _mode: PhantomData }
+                }
+
                 paste::paste! {
-                    /// Enable smitter GPIO input filter
-                    pub fn enable_smitter(&mut self) {
+                    /// Enable the Schmitt trigger on the GPIO input
+                    pub fn enable_schmitt(&mut self) {
                         let glb = unsafe { &*pac::GLB::ptr() };
 
                         glb.$gpio_cfgctli.modify(|_, w| w.[<reg_ $gpio_i _smt>]().set_bit());
                     }
 
-                    /// Enable smitter GPIO output filter
-                    pub fn disable_smitter(&mut self) {
+                    /// Disable the Schmitt trigger on the GPIO input.
+                    /// Converting the pin to another mode enables it again.
+                    pub fn disable_schmitt(&mut self) {
                         let glb = unsafe { &*pac::GLB::ptr() };
 
                         glb.$gpio_cfgctli.modify(|_, w| w.[<reg_ $gpio_i _smt>]().clear_bit());
                     }
+
+                    /// Set the output drive strength.
+                    /// Converting the pin to another mode resets it to `Level0`.
+                    pub fn set_drive_strength(&mut self, strength: DriveStrength) {
+                        let glb = unsafe { &*pac::GLB::ptr() };
+
+                        glb.$gpio_cfgctli.modify(|_, w| unsafe {
+                            w.[<reg_ $gpio_i _drv>]().bits(strength as u8)
+                        });
+                    }
+
+                    fn set_output_value(&self, high: bool) {
+                        let glb = unsafe { &*pac::GLB::ptr() };
+                        glb.gpio_cfgctl32.modify(|_, w| w.[<reg_ $gpio_i _o>]().bit(high));
+                    }
+
+                    fn set_output_enable(&self, enable: bool) {
+                        let glb = unsafe { &*pac::GLB::ptr() };
+                        glb.gpio_cfgctl34.modify(|_, w| w.[<reg_ $gpio_i _oe>]().bit(enable));
+                    }
+                }
+            }
+
+            impl<MODE> $Pini<Input<MODE>> {
+                /// Enable the Schmitt trigger on the GPIO input
+                #[deprecated(note = "use enable_schmitt")]
+                pub fn enable_smitter(&mut self) {
+                    self.enable_schmitt()
+                }
+
+                /// Disable the Schmitt trigger on the GPIO input
+                #[deprecated(note = "use disable_schmitt")]
+                pub fn disable_smitter(&mut self) {
+                    self.disable_schmitt()
                 }
             }
 
@@ -405,12 +661,19 @@ macro_rules! impl_glb {
                         // 6 -> GPIO_FUN_I2C_x
                         self.into_pin_with_mode(6, true, false, true)
                     }
+
+                    /// Configures the pin to output a clock.
+                    /// Even pins output CLK_OUT_0 and odd pins CLK_OUT_1, see [`ClkCfg`].
+                    pub fn into_clock_out(self) -> $Pini<ClkOut> {
+                        // 0 -> GPIO_FUN_CLK_OUT
+                        self.into_pin_with_mode(0, false, false, false)
+                    }
                 }
             }
 
             impl UartPin<$UartSigi> for $Pini<Uart> {}
 
-            impl<MODE> InternalInputPinImpl for $Pini<Input<MODE>> {
+            impl<MODE> InternalInputPinImpl for $Pini<MODE> {
                 paste::paste! {
                     fn is_high_inner(&self) -> bool {
                         let glb = unsafe { &*pac::GLB::ptr() };
@@ -425,31 +688,39 @@ macro_rules! impl_glb {
                 }
             }
 
-            impl<MODE> InternalOutputPinImp for $Pini<Output<MODE>> {
-                paste::paste! {
-                    fn set_high_inner(&self) {
-                        let glb = unsafe { &*pac::GLB::ptr() };
-                        glb.gpio_cfgctl32.modify(|_, w| w.[<reg_ $gpio_i _o>]().set_bit())
+            impl<MODE: OutputMode> InternalOutputPinImp for $Pini<Output<MODE>> {
+                fn set_high_inner(&self) {
+                    if MODE::OPEN_DRAIN {
+                        // Release the line and let the pull-up take it high
+                        self.set_output_enable(false)
+                    } else {
+                        self.set_output_value(true)
                     }
                 }
-                paste::paste! {
-                    fn set_low_inner(&self)  {
-                        let glb = unsafe { &*pac::GLB::ptr() };
-                        glb.gpio_cfgctl32.modify(|_, w| w.[<reg_ $gpio_i _o>]().clear_bit())
+
+                fn set_low_inner(&self)  {
+                    if MODE::OPEN_DRAIN {
+                        // The output value is held low, drive it
+                        self.set_output_enable(true)
+                    } else {
+                        self.set_output_value(false)
                     }
                 }
             }
 
-            impl<MODE> InternalStatefulOutputImp for $Pini<Output<MODE>> {
+            impl<MODE: OutputMode> InternalStatefulOutputImp for $Pini<Output<MODE>> {
                 paste::paste! {
                     fn is_output_high_inner(&self) -> bool {
                         let glb = unsafe { &*pac::GLB::ptr() };
-                        glb.gpio_cfgctl32.read().[<reg_ $gpio_i _o>]().bit_is_set()
+                        if MODE::OPEN_DRAIN {
+                            glb.gpio_cfgctl34.read().[<reg_ $gpio_i _oe>]().bit_is_clear()
+                        } else {
+                            glb.gpio_cfgctl32.read().[<reg_ $gpio_i _o>]().bit_is_set()
+                        }
                     }
 
                     fn is_output_low_inner(& self) -> bool {
-                        let glb = unsafe { &*pac::GLB::ptr() };
-                        glb.gpio_cfgctl32.read().[<reg_ $gpio_i _o>]().bit_is_clear()
+                        !self.is_output_high_inner()
                     }
                 }
             }
@@ -479,88 +750,84 @@ macro_rules! impl_glb {
                 }
             }
 
-            impl<MODE> InterruptPin for $Pini<Input<MODE>> {
+            impl InputPin for $Pini<Output<OpenDrain>> {
+                type Error = Infallible;
 
-                paste::paste! {
-                    fn trigger_on_event(&mut self, event: Event) {
-                        let glb = unsafe { &*pac::GLB::ptr() };
-                        // 10 pins per register
-                        let pin_id = $pin_id % 10;
-                        // each pin uses 3 bits
-                        let offset = pin_id * 3;
-                        // ensure our event fits in the mask
-                        let event = (0b11 & event as u32) << offset;
-                        // Write new event
-                        glb.$gpio_int_mode_seti.modify(|r,w| {
-                            // Mask off previous bits
-                            let mask = 0b11 << offset;
-                            let prev = r.bits() & ! mask;
-                            unsafe {w.bits(prev | event)}
-                        });
-                    }
+                fn is_high(&self) -> Result<bool, Self::Error> {
+                    Ok(self.is_high_inner())
+                }
 
-                    fn control_asynchronous(&mut self) {
-                        let glb = unsafe { &*pac::GLB::ptr() };
-                        let pin_id = $pin_id % 10;
-                        let offset = pin_id * 3;
-                        glb.$gpio_int_mode_seti.modify(|r,w| {
-                            let mask = 0b100 << offset;
-                            unsafe {w.bits(r.bits() | mask)}
-                        });
-                    }
+                fn is_low(&self) -> Result<bool, Self::Error> {
+                    Ok(self.is_low_inner())
+                }
+            }
 
-                    fn control_synchronous(&mut self) {
-                        let glb = unsafe { &*pac::GLB::ptr() };
-                        let pin_id = $pin_id % 10;
-                        // each pin uses 3 bits
-                        let offset = pin_id * 3;
-                        glb.gpio_int_mode_set1.modify(|r,w| {
-                            let mask = 0b100 << offset;
-                            unsafe {w.bits(r.bits() & !mask)}
-                        });
-                    }
+            impl InputPinZero for $Pini<Output<OpenDrain>> {
+                type Error = Infallible;
 
-                    fn enable_interrupt(&mut self) {
-                        let glb = unsafe { &*pac::GLB::ptr() };
+                fn is_high(&self) -> Result<bool, Self::Error> {
+                    Ok(self.is_high_inner())
+                }
 
-                        glb.gpio_int_mask1.modify(|r,w| {
-                            unsafe {
-                            w.bits(r.bits() | (1 << $pin_id))
-                            }
-                        });
-                    }
+                fn is_low(&self) -> Result<bool, Self::Error> {
+                    Ok(self.is_low_inner())
+                }
+            }
 
-                    fn disable_interrupt(&mut self) {
-                        let glb = unsafe { &*pac::GLB::ptr() };
+            impl<MODE> InterruptPin for $Pini<Input<MODE>> {
+                fn trigger_on_event(&mut self, event: Event) {
+                    modify_int_mode($pin_id, |bits| int_bits::with_trigger(bits, $pin_id, event));
+                }
 
-                        glb.gpio_int_mask1.modify(|r, w| {
-                            unsafe {
-                                w.bits(r.bits() & (0xFFFF_FFFF - (1 << $pin_id)))
-                            }
-                        });
-                    }
+                fn control_asynchronous(&mut self) {
+                    modify_int_mode($pin_id, |bits| int_bits::with_control(bits, $pin_id, true));
+                }
 
-                    fn clear_interrupt_pending_bit(&mut self) {
-                        let glb = unsafe { &*pac::GLB::ptr() };
+                fn control_synchronous(&mut self) {
+                    modify_int_mode($pin_id, |bits| int_bits::with_control(bits, $pin_id, false));
+                }
 
-                        glb.gpio_int_clr1.write(|w| {
-                            unsafe {
-                            w.bits(1 << $pin_id)
-                            }
-                        });
-                    }
+                fn enable_interrupt(&mut self) {
+                    let glb = unsafe { &*pac::GLB::ptr() };
+                    // The mask register is shared by all pins
+                    riscv::interrupt::free(|| {
+                        glb.gpio_int_mask1.modify(|r, w| unsafe {
+                            w.bits(int_bits::with_enabled(r.bits(), $pin_id, true))
+                        })
+                    });
+                }
 
-                    fn check_interrupt(&self) -> bool {
-                        let glb = unsafe { &*pac::GLB::ptr() };
-                        let intstat:u32 = glb.gpio_int_stat1.read().gpio_int_stat1().bits();
-                        let int_masked:u32 = intstat | (1 << $pin_id);
-                        0 != int_masked
-                    }
+                fn disable_interrupt(&mut self) {
+                    let glb = unsafe { &*pac::GLB::ptr() };
+                    // The mask register is shared by all pins
+                    riscv::interrupt::free(|| {
+                        glb.gpio_int_mask1.modify(|r, w| unsafe {
+                            w.bits(int_bits::with_enabled(r.bits(), $pin_id, false))
+                        })
+                    });
                 }
-            }
 
+                fn clear_interrupt_pending_bit(&mut self) {
+                    let glb = unsafe { &*pac::GLB::ptr() };
+                    // The clear bit holds the status low while set, so release it again
+                    // or no further interrupts will be latched for this pin
+                    riscv::interrupt::free(|| {
+                        glb.gpio_int_clr1.modify(|r, w| unsafe { w.bits(r.bits() | (1 << $pin_id)) });
+                        glb.gpio_int_clr1.modify(|r, w| unsafe { w.bits(r.bits() & !(1 << $pin_id)) });
+                    });
+                }
 
-            impl<MODE> OutputPin for $Pini<Output<MODE>> {
+                fn is_pending(&self) -> bool {
+                    let glb = unsafe { &*pac::GLB::ptr() };
+                    glb.gpio_int_stat1.read().gpio_int_stat1().bits() & (1 << $pin_id) != 0
+                }
+
+                fn check_interrupt(&self) -> bool {
+                    self.is_pending()
+                }
+            }
+
+            impl<MODE: OutputMode> OutputPin for $Pini<Output<MODE>> {
                 type Error = Infallible;
 
                 fn set_high(&mut self) -> Result<(), Self::Error> {
@@ -574,7 +841,7 @@ macro_rules! impl_glb {
                 }
             }
 
-            impl<MODE> OutputPinZero for $Pini<Output<MODE>> {
+            impl<MODE: OutputMode> OutputPinZero for $Pini<Output<MODE>> {
                 type Error = Infallible;
 
                 fn set_high(&mut self) -> Result<(), Self::Error> {
@@ -588,7 +855,7 @@ macro_rules! impl_glb {
                 }
             }
 
-            impl<MODE> StatefulOutputPin for $Pini<Output<MODE>> {
+            impl<MODE: OutputMode> StatefulOutputPin for $Pini<Output<MODE>> {
                 fn is_set_high(&self) -> Result<bool, Self::Error> {
                     Ok(self.is_output_high_inner())
                 }
@@ -598,7 +865,7 @@ macro_rules! impl_glb {
                 }
             }
 
-            impl<MODE> StatefulOutputPinZero for $Pini<Output<MODE>> {
+            impl<MODE: OutputMode> StatefulOutputPinZero for $Pini<Output<MODE>> {
                 fn is_set_high(&self) -> Result<bool, Self::Error> {
                     Ok(self.is_output_high_inner())
                 }
@@ -609,7 +876,7 @@ macro_rules! impl_glb {
             }
 
 
-            impl<MODE> ToggleableOutputPin for $Pini<Output<MODE>> {
+            impl<MODE: OutputMode> ToggleableOutputPin for $Pini<Output<MODE>> {
                 type Error = Infallible;
 
                 fn toggle(&mut self) -> Result<(), Self::Error> {
@@ -622,7 +889,7 @@ macro_rules! impl_glb {
                 }
             }
 
-            impl<MODE> ToggleableOutputPinZero for $Pini<Output<MODE>> {
+            impl<MODE: OutputMode> ToggleableOutputPinZero for $Pini<Output<MODE>> {
                 type Error = Infallible;
 
                 fn toggle(&mut self) -> Result<(), Self::Error> {
@@ -644,36 +911,542 @@ macro_rules! impl_glb {
 // there are at most 32 GPIO, the other 6 pins are analogue only
 // TODO: implement the rest of the pins
 impl_glb! {
-    Pin0: (pin0, gpio_cfgctl0, UartSig0, sig0, mosi, scl, gpio_0, gpio_int_mode_set1, 0),
-    Pin1: (pin1, gpio_cfgctl0, UartSig1, sig1, miso, sda, gpio_1, gpio_int_mode_set1, 1),
-    Pin2: (pin2, gpio_cfgctl1, UartSig2, sig2, ss, scl, gpio_2, gpio_int_mode_set1, 2),
-    Pin3: (pin3, gpio_cfgctl1, UartSig3, sig3, sclk, sda, gpio_3, gpio_int_mode_set1, 3),
-    Pin4: (pin4, gpio_cfgctl2, UartSig4, sig4, mosi, scl, gpio_4, gpio_int_mode_set1, 4),
-    Pin5: (pin5, gpio_cfgctl2, UartSig5, sig5, miso, sda, gpio_5, gpio_int_mode_set1, 5),
-    Pin6: (pin6, gpio_cfgctl3, UartSig6, sig6, ss, scl, gpio_6, gpio_int_mode_set1, 6),
-    Pin7: (pin7, gpio_cfgctl3, UartSig7, sig7, sclk, sda, gpio_7, gpio_int_mode_set1, 7),
-    Pin8: (pin8, gpio_cfgctl4, UartSig0, sig0, mosi, scl, gpio_8, gpio_int_mode_set1, 8),
-    Pin9: (pin9, gpio_cfgctl4, UartSig1, sig1, miso, sda, gpio_9, gpio_int_mode_set1, 9),
-    Pin10: (pin10, gpio_cfgctl5, UartSig2, sig2, ss, scl, gpio_10, gpio_int_mode_set2, 10),
-    Pin11: (pin11, gpio_cfgctl5, UartSig3, sig3, sclk, sda, gpio_11, gpio_int_mode_set2, 11),
-    Pin12: (pin12, gpio_cfgctl6, UartSig4, sig4, mosi, scl, gpio_12, gpio_int_mode_set2, 12),
-    Pin13: (pin13, gpio_cfgctl6, UartSig5, sig5, miso, sda, gpio_13, gpio_int_mode_set2, 13),
-    Pin14: (pin14, gpio_cfgctl7, UartSig6, sig6, ss, scl, gpio_14, gpio_int_mode_set2, 14),
-    Pin15: (pin15, gpio_cfgctl7, UartSig7, sig7, sclk, sda, gpio_15, gpio_int_mode_set2, 15),
-    Pin16: (pin16, gpio_cfgctl8, UartSig0, sig0, mosi, scl, gpio_16, gpio_int_mode_set2, 16),
-    Pin17: (pin17, gpio_cfgctl8, UartSig1, sig1, miso, sda, gpio_17, gpio_int_mode_set2, 17),
-    Pin18: (pin18, gpio_cfgctl9, UartSig2, sig2, ss, scl, gpio_18, gpio_int_mode_set2, 18),
-    Pin19: (pin19, gpio_cfgctl9, UartSig3, sig3, sclk, sda, gpio_19, gpio_int_mode_set2, 19),
-    Pin20: (pin20, gpio_cfgctl10, UartSig4, sig4, mosi, scl, gpio_20, gpio_int_mode_set3, 20),
-    Pin21: (pin21, gpio_cfgctl10, UartSig5, sig5, miso, sda, gpio_21, gpio_int_mode_set3, 21),
-    Pin22: (pin22, gpio_cfgctl11, UartSig6, sig6, ss, scl, gpio_22, gpio_int_mode_set3, 22),
-    Pin23: (pin23, gpio_cfgctl11, UartSig7, sig7, sclk, sda, gpio_23, gpio_int_mode_set3, 23),
-    Pin24: (pin24, gpio_cfgctl12, UartSig0, sig0, mosi, scl, gpio_24, gpio_int_mode_set3, 24),
-    Pin25: (pin25, gpio_cfgctl12, UartSig1, sig1, miso, sda, gpio_25, gpio_int_mode_set3, 25),
-    Pin26: (pin26, gpio_cfgctl13, UartSig2, sig2, ss, scl, gpio_26, gpio_int_mode_set3, 26),
-    Pin27: (pin27, gpio_cfgctl13, UartSig3, sig3, sclk, sda, gpio_27, gpio_int_mode_set3, 27),
-    Pin28: (pin28, gpio_cfgctl14, UartSig4, sig4, mosi, scl, gpio_28, gpio_int_mode_set3, 28),
-    Pin29: (pin29, gpio_cfgctl14, UartSig5, sig5, miso, sda, gpio_29, gpio_int_mode_set3, 29),
-    Pin30: (pin30, gpio_cfgctl15, UartSig6, sig6, ss, scl, gpio_30, gpio_int_mode_set4, 30),
-    Pin31: (pin31, gpio_cfgctl15, UartSig7, sig7, sclk, sda, gpio_31, gpio_int_mode_set4, 31),
+    Pin0: (pin0, gpio_cfgctl0, UartSig0, sig0, mosi, scl, gpio_0, 0),
+    Pin1: (pin1, gpio_cfgctl0, UartSig1, sig1, miso, sda, gpio_1, 1),
+    Pin2: (pin2, gpio_cfgctl1, UartSig2, sig2, ss, scl, gpio_2, 2),
+    Pin3: (pin3, gpio_cfgctl1, UartSig3, sig3, sclk, sda, gpio_3, 3),
+    Pin4: (pin4, gpio_cfgctl2, UartSig4, sig4, mosi, scl, gpio_4, 4),
+    Pin5: (pin5, gpio_cfgctl2, UartSig5, sig5, miso, sda, gpio_5, 5),
+    Pin6: (pin6, gpio_cfgctl3, UartSig6, sig6, ss, scl, gpio_6, 6),
+    Pin7: (pin7, gpio_cfgctl3, UartSig7, sig7, sclk, sda, gpio_7, 7),
+    Pin8: (pin8, gpio_cfgctl4, UartSig0, sig0, mosi, scl, gpio_8, 8),
+    Pin9: (pin9, gpio_cfgctl4, UartSig1, sig1, miso, sda, gpio_9, 9),
+    Pin10: (pin10, gpio_cfgctl5, UartSig2, sig2, ss, scl, gpio_10, 10),
+    Pin11: (pin11, gpio_cfgctl5, UartSig3, sig3, sclk, sda, gpio_11, 11),
+    Pin12: (pin12, gpio_cfgctl6, UartSig4, sig4, mosi, scl, gpio_12, 12),
+    Pin13: (pin13, gpio_cfgctl6, UartSig5, sig5, miso, sda, gpio_13, 13),
+    Pin14: (pin14, gpio_cfgctl7, UartSig6, sig6, ss, scl, gpio_14, 14),
+    Pin15: (pin15, gpio_cfgctl7, UartSig7, sig7, sclk, sda, gpio_15, 15),
+    Pin16: (pin16, gpio_cfgctl8, UartSig0, sig0, mosi, scl, gpio_16, 16),
+    Pin17: (pin17, gpio_cfgctl8, UartSig1, sig1, miso, sda, gpio_17, 17),
+    Pin18: (pin18, gpio_cfgctl9, UartSig2, sig2, ss, scl, gpio_18, 18),
+    Pin19: (pin19, gpio_cfgctl9, UartSig3, sig3, sclk, sda, gpio_19, 19),
+    Pin20: (pin20, gpio_cfgctl10, UartSig4, sig4, mosi, scl, gpio_20, 20),
+    Pin21: (pin21, gpio_cfgctl10, UartSig5, sig5, miso, sda, gpio_21, 21),
+    Pin22: (pin22, gpio_cfgctl11, UartSig6, sig6, ss, scl, gpio_22, 22),
+    Pin23: (pin23, gpio_cfgctl11, UartSig7, sig7, sclk, sda, gpio_23, 23),
+    Pin24: (pin24, gpio_cfgctl12, UartSig0, sig0, mosi, scl, gpio_24, 24),
+    Pin25: (pin25, gpio_cfgctl12, UartSig1, sig1, miso, sda, gpio_25, 25),
+    Pin26: (pin26, gpio_cfgctl13, UartSig2, sig2, ss, scl, gpio_26, 26),
+    Pin27: (pin27, gpio_cfgctl13, UartSig3, sig3, sclk, sda, gpio_27, 27),
+    Pin28: (pin28, gpio_cfgctl14, UartSig4, sig4, mosi, scl, gpio_28, 28),
+    Pin29: (pin29, gpio_cfgctl14, UartSig5, sig5, miso, sda, gpio_29, 29),
+    Pin30: (pin30, gpio_cfgctl15, UartSig6, sig6, ss, scl, gpio_30, 30),
+    Pin31: (pin31, gpio_cfgctl15, UartSig7, sig7, sclk, sda, gpio_31, 31),
+}
+
+/// Type-erased pin, carrying the pin number at runtime. Created with `degrade()`.
+pub struct AnyPin<MODE> {
+    pin: u8,
+    _mode: PhantomData<MODE>,
+}
+
+impl<MODE> AnyPin<MODE> {
+    /// The GPIO number of this pin
+    pub fn pin_id(&self) -> u8 {
+        self.pin
+    }
+
+    /// Configures the pin to operate as a Hi-Z floating output pin.
+    pub fn into_floating_output(self) -> AnyPin<Output<Floating>> {
+        self.into_pin_with_mode(11, false, false, false)
+    }
+
+    /// Configures the pin to operate as a pull-up output pin.
+    pub fn into_pull_up_output(self) -> AnyPin<Output<PullUp>> {
+        self.into_pin_with_mode(11, true, false, false)
+    }
+
+    /// Configures the pin to operate as a pull-down output pin.
+    pub fn into_pull_down_output(self) -> AnyPin<Output<PullDown>> {
+        self.into_pin_with_mode(11, false, true, false)
+    }
+
+    /// Configures the pin to operate as an open-drain output pin, initially released (high).
+    pub fn into_open_drain_output(self) -> AnyPin<Output<OpenDrain>> {
+        let pin: AnyPin<Output<OpenDrain>> = self.into_pin_with_mode(11, false, false, true);
+        pin.set_output_value(false);
+        pin
+    }
+
+    /// Configures the pin to operate as a Hi-Z floating input pin.
+    pub fn into_floating_input(self) -> AnyPin<Input<Floating>> {
+        self.into_pin_with_mode(11, false, false, true)
+    }
+
+    /// Configures the pin to operate as a pull-up input pin.
+    pub fn into_pull_up_input(self) -> AnyPin<Input<PullUp>> {
+        self.into_pin_with_mode(11, true, false, true)
+    }
+
+    /// Configures the pin to operate as a pull-down input pin.
+    pub fn into_pull_down_input(self) -> AnyPin<Input<PullDown>> {
+        self.into_pin_with_mode(11, false, true, true)
+    }
+
+    /// Enable the Schmitt trigger on the GPIO input
+    pub fn enable_schmitt(&mut self) {
+        self.modify_cfgctl(|cfg| cfg | 1 << 1);
+    }
+
+    /// Disable the Schmitt trigger on the GPIO input.
+    /// Converting the pin to another mode enables it again.
+    pub fn disable_schmitt(&mut self) {
+        self.modify_cfgctl(|cfg| cfg & !(1 << 1));
+    }
+
+    /// Set the output drive strength.
+    /// Converting the pin to another mode resets it to `Level0`.
+    pub fn set_drive_strength(&mut self, strength: DriveStrength) {
+        self.modify_cfgctl(|cfg| (cfg & !(0b11 << 2)) | (strength as u32) << 2);
+    }
+
+    /// Read-modify-write this pin's half of its `gpio_cfgctlN` register
+    fn modify_cfgctl(&self, f: impl FnOnce(u32) -> u32) {
+        let glb = unsafe { &*pac::GLB::ptr() };
+        // Two pins per register, odd pins use the upper half
+        let reg = (&glb.gpio_cfgctl0 as *const _ as *mut u32).wrapping_add(self.pin as usize / 2);
+        let shift = (self.pin as u32 % 2) * 16;
+        riscv::interrupt::free(|| unsafe {
+            let val = reg.read_volatile();
+            let cfg = f((val >> shift) & 0xffff);
+            reg.write_volatile((val & !(0xffff << shift)) | ((cfg & 0xffff) << shift));
+        });
+    }
+
+    #[inline]
+    fn into_pin_with_mode<T>(self, mode: u8, pu: bool, pd: bool, ie: bool) -> AnyPin<T> {
+        // ie: bit 0, smt: bit 1, drv: bits 2:3, pu: bit 4, pd: bit 5, func_sel: bits 8:12
+        self.modify_cfgctl(|cfg| {
+            let cfg = cfg & !(0x1f << 8 | 1 << 5 | 1 << 4 | 0b11 << 2);
+            // keep input buffer active, github issue #8
+            cfg | (mode as u32) << 8 | (pd as u32) << 5 | (pu as u32) << 4 | 1 << 1 | 1
+        });
+
+        // If we're an input clear the Output Enable bit as well, else set it.
+        let glb = unsafe { &*pac::GLB::ptr() };
+        let mask = 1 << self.pin;
+        glb.gpio_cfgctl34.modify(|r, w| unsafe {
+            if ie {
+                w.bits(r.bits() & !mask)
+            } else {
+                w.bits(r.bits() | mask)
+            }
+        });
+
+        AnyPin {
+            pin: self.pin,
+            _mode: PhantomData,
+        }
+    }
+
+    fn is_high_inner(&self) -> bool {
+        let glb = unsafe { &*pac::GLB::ptr() };
+        glb.gpio_cfgctl30.read().bits() & (1 << self.pin) != 0
+    }
+
+    fn set_output_value(&self, high: bool) {
+        let glb = unsafe { &*pac::GLB::ptr() };
+        let mask = 1 << self.pin;
+        glb.gpio_cfgctl32.modify(|r, w| unsafe {
+            if high {
+                w.bits(r.bits() | mask)
+            } else {
+                w.bits(r.bits() & !mask)
+            }
+        });
+    }
+
+    fn set_output_enable(&self, enable: bool) {
+        let glb = unsafe { &*pac::GLB::ptr() };
+        let mask = 1 << self.pin;
+        glb.gpio_cfgctl34.modify(|r, w| unsafe {
+            if enable {
+                w.bits(r.bits() | mask)
+            } else {
+                w.bits(r.bits() & !mask)
+            }
+        });
+    }
+}
+
+impl<MODE: OutputMode> AnyPin<Output<MODE>> {
+    fn is_output_high_inner(&self) -> bool {
+        let glb = unsafe { &*pac::GLB::ptr() };
+        if MODE::OPEN_DRAIN {
+            glb.gpio_cfgctl34.read().bits() & (1 << self.pin) == 0
+        } else {
+            glb.gpio_cfgctl32.read().bits() & (1 << self.pin) != 0
+        }
+    }
+
+    fn set_output_inner(&self, high: bool) {
+        if MODE::OPEN_DRAIN {
+            self.set_output_enable(!high)
+        } else {
+            self.set_output_value(high)
+        }
+    }
+}
+
+impl<MODE> embedded_hal_alpha::digital::blocking::InputPin for AnyPin<Input<MODE>> {
+    type Error = core::convert::Infallible;
+
+    fn is_high(&self) -> Result<bool, Self::Error> {
+        Ok(self.is_high_inner())
+    }
+
+    fn is_low(&self) -> Result<bool, Self::Error> {
+        Ok(!self.is_high_inner())
+    }
+}
+
+impl<MODE> embedded_hal::digital::v2::InputPin for AnyPin<Input<MODE>> {
+    type Error = core::convert::Infallible;
+
+    fn is_high(&self) -> Result<bool, Self::Error> {
+        Ok(self.is_high_inner())
+    }
+
+    fn is_low(&self) -> Result<bool, Self::Error> {
+        Ok(!self.is_high_inner())
+    }
+}
+
+impl embedded_hal_alpha::digital::blocking::InputPin for AnyPin<Output<OpenDrain>> {
+    type Error = core::convert::Infallible;
+
+    fn is_high(&self) -> Result<bool, Self::Error> {
+        Ok(self.is_high_inner())
+    }
+
+    fn is_low(&self) -> Result<bool, Self::Error> {
+        Ok(!self.is_high_inner())
+    }
+}
+
+impl embedded_hal::digital::v2::InputPin for AnyPin<Output<OpenDrain>> {
+    type Error = core::convert::Infallible;
+
+    fn is_high(&self) -> Result<bool, Self::Error> {
+        Ok(self.is_high_inner())
+    }
+
+    fn is_low(&self) -> Result<bool, Self::Error> {
+        Ok(!self.is_high_inner())
+    }
+}
+
+impl<MODE: OutputMode> embedded_hal_alpha::digital::blocking::OutputPin for AnyPin<Output<MODE>> {
+    type Error = core::convert::Infallible;
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.set_output_inner(true);
+        Ok(())
+    }
+
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.set_output_inner(false);
+        Ok(())
+    }
+}
+
+impl<MODE: OutputMode> embedded_hal::digital::v2::OutputPin for AnyPin<Output<MODE>> {
+    type Error = core::convert::Infallible;
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.set_output_inner(true);
+        Ok(())
+    }
+
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.set_output_inner(false);
+        Ok(())
+    }
+}
+
+impl<MODE: OutputMode> embedded_hal_alpha::digital::blocking::StatefulOutputPin
+    for AnyPin<Output<MODE>>
+{
+    fn is_set_high(&self) -> Result<bool, Self::Error> {
+        Ok(self.is_output_high_inner())
+    }
+
+    fn is_set_low(&self) -> Result<bool, Self::Error> {
+        Ok(!self.is_output_high_inner())
+    }
+}
+
+impl<MODE: OutputMode> embedded_hal::digital::v2::StatefulOutputPin for AnyPin<Output<MODE>> {
+    fn is_set_high(&self) -> Result<bool, Self::Error> {
+        Ok(self.is_output_high_inner())
+    }
+
+    fn is_set_low(&self) -> Result<bool, Self::Error> {
+        Ok(!self.is_output_high_inner())
+    }
+}
+
+impl<MODE: OutputMode> embedded_hal_alpha::digital::blocking::ToggleableOutputPin
+    for AnyPin<Output<MODE>>
+{
+    type Error = core::convert::Infallible;
+
+    fn toggle(&mut self) -> Result<(), Self::Error> {
+        self.set_output_inner(!self.is_output_high_inner());
+        Ok(())
+    }
+}
+
+impl<MODE: OutputMode> embedded_hal::digital::v2::ToggleableOutputPin for AnyPin<Output<MODE>> {
+    type Error = core::convert::Infallible;
+
+    fn toggle(&mut self) -> Result<(), Self::Error> {
+        self.set_output_inner(!self.is_output_high_inner());
+        Ok(())
+    }
+}
+
+/// A group of pins read or written together, e.g. a parallel data bus.
+///
+/// Bit `i` of the port value maps to `pins[i]`. The port owns its pins, so they can't be
+/// driven from elsewhere while it exists.
+pub struct Port<MODE, const N: usize> {
+    pins: [AnyPin<MODE>; N],
+    /// GPIO mask of all pins in the port
+    mask: u32,
+    /// Set if the pins are consecutive GPIOs in ascending order, the lowest pin number
+    shift: Option<u8>,
+}
+
+impl<MODE, const N: usize> Port<MODE, N> {
+    /// Group pins into a port; all pins must be distinct
+    pub fn new(pins: [AnyPin<MODE>; N]) -> Self {
+        assert!(N <= 32);
+        let mut mask: u32 = 0;
+        for pin in pins.iter() {
+            mask |= 1 << pin.pin;
+        }
+        debug_assert_eq!(mask.count_ones() as usize, N, "port pins must be distinct");
+        let contiguous = pins
+            .iter()
+            .enumerate()
+            .all(|(i, pin)| pin.pin as usize == pins[0].pin as usize + i);
+        let shift = if N > 0 && contiguous {
+            Some(pins[0].pin)
+        } else {
+            None
+        };
+        Port { pins, mask, shift }
+    }
+
+    /// Release the pins
+    pub fn free(self) -> [AnyPin<MODE>; N] {
+        self.pins
+    }
+
+    /// GPIO mask of the pins in this port
+    pub fn gpio_mask(&self) -> u32 {
+        self.mask
+    }
+
+    /// Convert a port value to the GPIO bit positions of its pins
+    fn spread(&self, value: u32) -> u32 {
+        match self.shift {
+            Some(shift) => (value << shift) & self.mask,
+            None => self
+                .pins
+                .iter()
+                .enumerate()
+                .fold(0, |acc, (i, pin)| acc | (((value >> i) & 1) << pin.pin)),
+        }
+    }
+
+    /// Gather the GPIO bits of the pins into a port value
+    fn gather(&self, gpio: u32) -> u32 {
+        match self.shift {
+            Some(shift) => (gpio & self.mask) >> shift,
+            None => self
+                .pins
+                .iter()
+                .enumerate()
+                .fold(0, |acc, (i, pin)| acc | (((gpio >> pin.pin) & 1) << i)),
+        }
+    }
+}
+
+/// New value of the output value register (or output enable register for open drain
+/// ports), updating only the bits in `gpio_mask`
+fn port_output_bits(current: u32, gpio_value: u32, gpio_mask: u32, open_drain: bool) -> u32 {
+    // Open drain pins drive low by enabling the output, and release the line for high
+    let bits = if open_drain { !gpio_value } else { gpio_value };
+    (current & !gpio_mask) | (bits & gpio_mask)
+}
+
+impl<MODE, const N: usize> Port<Input<MODE>, N> {
+    /// Read all pins at once
+    pub fn read(&self) -> u32 {
+        let glb = unsafe { &*pac::GLB::ptr() };
+        self.gather(glb.gpio_cfgctl30.read().bits())
+    }
+}
+
+impl<MODE: OutputMode, const N: usize> Port<Output<MODE>, N> {
+    /// Write all pins at once
+    pub fn write(&mut self, value: u32) {
+        self.write_masked(value, u32::MAX);
+    }
+
+    /// Write the pins selected by `mask` (in port bit positions) at once, leaving the others
+    pub fn write_masked(&mut self, value: u32, mask: u32) {
+        let glb = unsafe { &*pac::GLB::ptr() };
+        let gpio_mask = self.spread(mask);
+        let gpio_value = self.spread(value);
+        // The output registers are shared with every other pin, keep other owners out
+        riscv::interrupt::free(|| {
+            if MODE::OPEN_DRAIN {
+                glb.gpio_cfgctl34.modify(|r, w| unsafe {
+                    w.bits(port_output_bits(r.bits(), gpio_value, gpio_mask, true))
+                });
+            } else {
+                glb.gpio_cfgctl32.modify(|r, w| unsafe {
+                    w.bits(port_output_bits(r.bits(), gpio_value, gpio_mask, false))
+                });
+            }
+        });
+    }
+
+    /// Read back the output values last written
+    pub fn read_output(&self) -> u32 {
+        let glb = unsafe { &*pac::GLB::ptr() };
+        if MODE::OPEN_DRAIN {
+            self.gather(!glb.gpio_cfgctl34.read().bits())
+        } else {
+            self.gather(glb.gpio_cfgctl32.read().bits())
+        }
+    }
+}
+
+impl<const N: usize> Port<Output<OpenDrain>, N> {
+    /// Read the line levels of all pins at once
+    pub fn read(&self) -> u32 {
+        let glb = unsafe { &*pac::GLB::ptr() };
+        self.gather(glb.gpio_cfgctl30.read().bits())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{int_bits, port_output_bits, AnyPin, Event, Port};
+    use core::marker::PhantomData;
+
+    fn port<const N: usize>(pins: [u8; N]) -> Port<(), N> {
+        Port::new(pins.map(|pin| AnyPin {
+            pin,
+            _mode: PhantomData,
+        }))
+    }
+
+    #[test]
+    fn int_mode_register_selection() {
+        assert_eq!(int_bits::mode_register(0), 0);
+        assert_eq!(int_bits::mode_register(9), 0);
+        assert_eq!(int_bits::mode_register(10), 1);
+        assert_eq!(int_bits::mode_register(31), 3);
+    }
+
+    #[test]
+    fn int_mode_trigger_offset() {
+        assert_eq!(int_bits::with_trigger(0, 9, Event::HighLevel), 0b011 << 27);
+        assert_eq!(int_bits::with_trigger(0, 10, Event::HighLevel), 0b011);
+        assert_eq!(int_bits::with_trigger(0, 31, Event::HighLevel), 0b011 << 3);
+    }
+
+    #[test]
+    fn int_mode_trigger_keeps_control_and_neighbours() {
+        // pin 9 asynchronous with a level trigger, every other bit set
+        let bits = !(0b111 << 27) | (0b110 << 27);
+        let bits = int_bits::with_trigger(bits, 9, Event::PositivePulse);
+        assert_eq!(bits, !(0b111 << 27) | (0b101 << 27));
+        let bits = int_bits::with_trigger(bits, 9, Event::NegativePulse);
+        assert_eq!(bits, !(0b111 << 27) | (0b100 << 27));
+    }
+
+    #[test]
+    fn int_mode_control() {
+        let bits = int_bits::with_control(0b011 << 3, 31, true);
+        assert_eq!(bits, 0b111 << 3);
+        assert_eq!(int_bits::with_control(bits, 31, false), 0b011 << 3);
+        assert_eq!(int_bits::with_control(0, 10, true), 0b100);
+    }
+
+    #[test]
+    fn int_mask_polarity() {
+        assert_eq!(int_bits::with_enabled(u32::MAX, 10, true), !(1 << 10));
+        assert_eq!(int_bits::with_enabled(0, 31, false), 1 << 31);
+    }
+
+    #[test]
+    fn port_contiguous() {
+        let port = port([4, 5, 6, 7]);
+        assert_eq!(port.gpio_mask(), 0xf0);
+        assert_eq!(port.spread(0b1010), 0b1010_0000);
+        assert_eq!(port.spread(0xffff_ffff), 0xf0);
+        assert_eq!(port.gather(0xffff_ff5f), 0b0101);
+    }
+
+    #[test]
+    fn port_shift_at_pin_31() {
+        let port = port([31]);
+        assert_eq!(port.gpio_mask(), 1 << 31);
+        assert_eq!(port.spread(1), 1 << 31);
+        assert_eq!(port.spread(0b10), 0);
+        assert_eq!(port.gather(1 << 31), 1);
+        assert_eq!(port.gather(!(1 << 31)), 0);
+    }
+
+    #[test]
+    fn port_non_contiguous() {
+        // out of order and with gaps, bit i maps to pins[i]
+        let port = port([17, 3, 31, 0]);
+        assert_eq!(port.gpio_mask(), 1 << 17 | 1 << 3 | 1 << 31 | 1);
+        assert_eq!(port.spread(0b0001), 1 << 17);
+        assert_eq!(port.spread(0b0110), 1 << 3 | 1 << 31);
+        assert_eq!(port.spread(0b1000), 1);
+        assert_eq!(port.gather(1 << 31 | 1), 0b1100);
+        assert_eq!(port.gather(!port.gpio_mask()), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "port pins must be distinct")]
+    fn port_duplicate_pins() {
+        port([3, 3]);
+    }
+
+    #[test]
+    fn port_masked_write() {
+        let port = port([2, 9, 5]);
+        let mask = port.spread(0b101);
+        let value = port.spread(0b001);
+        // only pins 2 and 5 change, pin 9 and pins outside the port are kept
+        assert_eq!(
+            port_output_bits(1 << 9 | 1 << 5 | 1 << 20, value, mask, false),
+            1 << 9 | 1 << 2 | 1 << 20
+        );
+        // open drain drives the low pins by enabling their output
+        assert_eq!(
+            port_output_bits(1 << 9 | 1 << 20, value, mask, true),
+            1 << 9 | 1 << 5 | 1 << 20
+        );
+    }
 }
@@ -29,7 +29,7 @@
 //! ```
 //!
 
-#![no_std]
+#![cfg_attr(not(test), no_std)]
 
 pub use bl702_pac as pac;
 
@@ -43,6 +43,3 @@ pub mod prelude {
 }
 pub mod system;
 pub mod uart;
-
-/// System frequency (constant since we don't have clocks yet)
-pub const SYSFREQ: u32 = 144_000_000;
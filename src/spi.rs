@@ -29,6 +29,7 @@ use embedded_time::rate::Hertz;
 use crate::pac;
 
 use crate::clock::Clocks;
+use crate::system::glb;
 
 /// SPI error
 #[derive(Debug)]
@@ -42,6 +43,8 @@ pub enum Error {
     TxOverflow,
     /// Tx underflow occurred
     TxUnderflow,
+    /// The requested SCLK frequency can't be generated from the SPI clock
+    FrequencyOutOfRange,
 }
 
 /// The bit format to send the data in
@@ -127,6 +130,7 @@ where
 pub struct Spi<SPI, PINS> {
     spi: SPI,
     pins: PINS,
+    freq: Hertz<u32>,
 }
 
 impl<PINS> Spi<pac::SPI, PINS>
@@ -143,6 +147,8 @@ where
     where
         PINS: Pins<pac::SPI>,
     {
+        glb::peripheral_clock_enable(glb::BL_AHB_Slave1_Type::SPI);
+
         let glb = unsafe { &*pac::GLB::ptr() };
 
         glb.glb_parm.modify(|_r, w| {
@@ -151,27 +157,7 @@ where
             //.set_bit()
         });
 
-        // length of phase 0 and 1 (i.e. low / high values of SCLK)
-        // needs to be divided by two
-        let len = clocks.spi_clk().0 / freq.0 / 2;
-        if len > 256 || len == 0 {
-            panic!("Cannot reach the desired SPI frequency");
-        }
-
-        let len = (len - 1) as u8;
-        spi.spi_prd_0.modify(|_r, w| unsafe {
-            w.cr_spi_prd_s()
-                .bits(len)
-                .cr_spi_prd_p()
-                .bits(len)
-                .cr_spi_prd_d_ph_0()
-                .bits(len)
-                .cr_spi_prd_d_ph_1()
-                .bits(len)
-        });
-
-        spi.spi_prd_1
-            .modify(|_r, w| unsafe { w.cr_spi_prd_i().bits(len) });
+        set_period(&spi, freq, clocks).expect("Cannot reach the desired SPI frequency");
 
         spi.spi_config.modify(|_, w| unsafe {
             w.cr_spi_sclk_pol()
@@ -194,10 +180,20 @@ where
                 .set_bit() // master
         });
 
-        Spi { spi, pins }
+        Spi { spi, pins, freq }
     }
 
+    /// Recompute the SCLK period for the configured frequency after a clock change.
+    ///
+    /// Must not be called while a transfer is in progress. If the frequency
+    /// can't be reached from the new SPI clock, the old period is kept.
+    pub fn reclock(&mut self, clocks: Clocks) -> Result<(), Error> {
+        set_period(&self.spi, self.freq, clocks)
+    }
+
+    /// Release the SPI peripheral and pins, gating the SPI clock
     pub fn release(self) -> (pac::SPI, PINS) {
+        glb::peripheral_clock_disable(glb::BL_AHB_Slave1_Type::SPI);
         (self.spi, self.pins)
     }
 
@@ -223,6 +219,31 @@ where
     }
 }
 
+fn set_period(spi: &pac::SPI, freq: Hertz<u32>, clocks: Clocks) -> Result<(), Error> {
+    // length of phase 0 and 1 (i.e. low / high values of SCLK)
+    // needs to be divided by two
+    let len = clocks.spi_clk().0.checked_div(freq.0).unwrap_or(0) / 2;
+    if len > 256 || len == 0 {
+        return Err(Error::FrequencyOutOfRange);
+    }
+
+    let len = (len - 1) as u8;
+    spi.spi_prd_0.modify(|_r, w| unsafe {
+        w.cr_spi_prd_s()
+            .bits(len)
+            .cr_spi_prd_p()
+            .bits(len)
+            .cr_spi_prd_d_ph_0()
+            .bits(len)
+            .cr_spi_prd_d_ph_1()
+            .bits(len)
+    });
+
+    spi.spi_prd_1
+        .modify(|_r, w| unsafe { w.cr_spi_prd_i().bits(len) });
+    Ok(())
+}
+
 impl<PINS> FullDuplex<u8> for Spi<pac::SPI, PINS>
 where
     PINS: Pins<pac::SPI>,
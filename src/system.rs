@@ -1,3 +1,5 @@
+/// eFuse controller section
+pub mod ef_ctrl;
 /// Global Timer section
 pub mod glb;
 /// Hibernate section
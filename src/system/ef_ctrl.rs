@@ -0,0 +1,127 @@
+#![allow(non_camel_case_types, non_snake_case, clippy::upper_case_acronyms)]
+
+use crate::system::romfunc::{data::ROM_API_INDEX_e, rom_fn_ptr};
+
+/// RC32M trim value as stored in eFuse
+///
+/// bits 0:7 trim code, bit 8 parity, bit 9 trim code valid
+#[repr(C)]
+#[derive(Clone, Copy, Default)]
+pub struct Efuse_Ana_RC32M_Trim_Type(pub u32);
+
+impl Efuse_Ana_RC32M_Trim_Type {
+    pub fn trimRc32mCodeFrExt(&self) -> u8 {
+        (self.0 & 0xff) as u8
+    }
+
+    pub fn trimRc32mCodeFrExtParity(&self) -> u8 {
+        ((self.0 >> 8) & 1) as u8
+    }
+
+    pub fn trimRc32mExtCodeEn(&self) -> bool {
+        (self.0 >> 9) & 1 != 0
+    }
+}
+
+/// RC32K trim value as stored in eFuse
+///
+/// bits 0:9 trim code, bit 10 parity, bit 11 trim code valid
+#[repr(C)]
+#[derive(Clone, Copy, Default)]
+pub struct Efuse_Ana_RC32K_Trim_Type(pub u32);
+
+impl Efuse_Ana_RC32K_Trim_Type {
+    pub fn trimRc32kCodeFrExt(&self) -> u16 {
+        (self.0 & 0x3ff) as u16
+    }
+
+    pub fn trimRc32kCodeFrExtParity(&self) -> u8 {
+        ((self.0 >> 10) & 1) as u8
+    }
+
+    pub fn trimRc32kExtCodeEn(&self) -> bool {
+        (self.0 >> 11) & 1 != 0
+    }
+}
+
+// romfunc ((uint8_t(*)(uint32_t val, uint8_t len))ROM_APITABLE[ROM_API_INDEX_EF_Ctrl_Get_Trim_Parity])
+pub fn EF_Ctrl_Get_Trim_Parity(val: u32, len: u8) -> u8 {
+    if cfg!(feature = "reg_getters") {
+        return reg::EF_Ctrl_Get_Trim_Parity(val, len);
+    }
+    unsafe {
+        core::mem::transmute::<*const (), extern "C" fn(u32, u8) -> u8>(rom_fn_ptr(
+            ROM_API_INDEX_e::ROM_API_INDEX_EF_Ctrl_Get_Trim_Parity,
+        ))(val, len)
+    }
+}
+
+/// Local implementations of the ROM getters, used when the `reg_getters` feature is enabled
+pub mod reg {
+    /// Parity of the low `len` bits of `val`
+    pub fn EF_Ctrl_Get_Trim_Parity(val: u32, len: u8) -> u8 {
+        let mask = 1u32.checked_shl(len as u32).map_or(u32::MAX, |bit| bit - 1);
+        ((val & mask).count_ones() & 1) as u8
+    }
+}
+
+// romfunc ((void (*)(Efuse_Ana_RC32M_Trim_Type * trim)) ROM_APITABLE[ROM_API_INDEX_EF_Ctrl_Read_RC32M_Trim])
+pub fn EF_Ctrl_Read_RC32M_Trim(trim: &mut Efuse_Ana_RC32M_Trim_Type) {
+    unsafe {
+        core::mem::transmute::<*const (), extern "C" fn(*mut Efuse_Ana_RC32M_Trim_Type)>(
+            rom_fn_ptr(ROM_API_INDEX_e::ROM_API_INDEX_EF_Ctrl_Read_RC32M_Trim),
+        )(trim)
+    }
+}
+
+// romfunc ((void (*)(Efuse_Ana_RC32K_Trim_Type * trim)) ROM_APITABLE[ROM_API_INDEX_EF_Ctrl_Read_RC32K_Trim])
+pub fn EF_Ctrl_Read_RC32K_Trim(trim: &mut Efuse_Ana_RC32K_Trim_Type) {
+    unsafe {
+        core::mem::transmute::<*const (), extern "C" fn(*mut Efuse_Ana_RC32K_Trim_Type)>(
+            rom_fn_ptr(ROM_API_INDEX_e::ROM_API_INDEX_EF_Ctrl_Read_RC32K_Trim),
+        )(trim)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::system::romfunc::mock;
+
+    // Fake ROM parity, a bit-by-bit count like the C SDK
+    extern "C" fn rom_trim_parity(val: u32, len: u8) -> u8 {
+        let mut cnt = 0;
+        for i in 0..len.min(32) {
+            if val & (1 << i) != 0 {
+                cnt += 1;
+            }
+        }
+        cnt & 1
+    }
+
+    #[test]
+    fn reg_trim_parity_matches_rom() {
+        mock::install(
+            ROM_API_INDEX_e::ROM_API_INDEX_EF_Ctrl_Get_Trim_Parity,
+            rom_trim_parity as *const (),
+        );
+        let rom = unsafe {
+            core::mem::transmute::<*const (), extern "C" fn(u32, u8) -> u8>(rom_fn_ptr(
+                ROM_API_INDEX_e::ROM_API_INDEX_EF_Ctrl_Get_Trim_Parity,
+            ))
+        };
+        for val in [0, 1, 0x80, 0xff, 0x1ff, 0x3ff, 0x2a5, 0xffff_ffff] {
+            for len in [0, 1, 8, 10, 32] {
+                assert_eq!(
+                    reg::EF_Ctrl_Get_Trim_Parity(val, len),
+                    rom(val, len),
+                    "{:#x} {}",
+                    val,
+                    len
+                );
+            }
+        }
+        // the public wrapper returns the same, through the ROM or the local version
+        assert_eq!(EF_Ctrl_Get_Trim_Parity(0x2a5, 10), rom(0x2a5, 10));
+    }
+}
@@ -2,7 +2,10 @@
 
 use crate::system::romfunc::{data::ROM_API_INDEX_e, rom_fn_ptr};
 
-use super::{hbn::HBN_UART_CLK_Type, BL_Err_Type};
+use super::{
+    hbn::{HBN_Set_UART_CLK_Sel, HBN_UART_CLK_Type},
+    BL_Err_Type,
+};
 use crate::pac;
 
 /// Direct register pointer.
@@ -81,6 +84,9 @@ pub enum GLB_ROOT_CLK_Type {
 
 // romfunc ((GLB_ROOT_CLK_Type(*)(void))ROM_APITABLE[ROM_API_INDEX_GLB_Get_Root_CLK_Sel])
 pub fn GLB_Get_Root_CLK_Sel() -> GLB_ROOT_CLK_Type {
+    if cfg!(feature = "reg_getters") {
+        return reg::GLB_Get_Root_CLK_Sel();
+    }
     unsafe {
         core::mem::transmute::<*const (), extern "C" fn() -> GLB_ROOT_CLK_Type>(rom_fn_ptr(
             ROM_API_INDEX_e::ROM_API_INDEX_GLB_Get_Root_CLK_Sel,
@@ -90,6 +96,9 @@ pub fn GLB_Get_Root_CLK_Sel() -> GLB_ROOT_CLK_Type {
 
 // romfunc ((uint8_t(*)(void))ROM_APITABLE[ROM_API_INDEX_GLB_Get_HCLK_Div])
 pub fn GLB_Get_HCLK_Div() -> u8 {
+    if cfg!(feature = "reg_getters") {
+        return reg::GLB_Get_HCLK_Div();
+    }
     unsafe {
         core::mem::transmute::<*const (), extern "C" fn() -> u8>(rom_fn_ptr(
             ROM_API_INDEX_e::ROM_API_INDEX_GLB_Get_HCLK_Div,
@@ -99,15 +108,68 @@ pub fn GLB_Get_HCLK_Div() -> u8 {
 
 // romfunc ((uint8_t(*)(void))ROM_APITABLE[ROM_API_INDEX_GLB_Get_BCLK_Div])
 pub fn GLB_Get_BCLK_Div() -> u8 {
+    if cfg!(feature = "reg_getters") {
+        return reg::GLB_Get_BCLK_Div();
+    }
     unsafe {
         core::mem::transmute::<*const (), extern "C" fn() -> u8>(rom_fn_ptr(
-            ROM_API_INDEX_e::ROM_API_INDEX_GLB_Get_HCLK_Div,
+            ROM_API_INDEX_e::ROM_API_INDEX_GLB_Get_BCLK_Div,
         ))()
     }
 }
 
-pub fn GLB_Set_UART_CLK(_enable: u8, _clkSel: HBN_UART_CLK_Type, _div: u8) -> BL_Err_Type {
-    //TODO: impl uart clock set
+/// Register-level implementations of the ROM getters.
+///
+/// These are used instead of the ROM table when the `reg_getters` feature is enabled,
+/// and can be compared against the ROM results to check the wrappers.
+pub mod reg {
+    use super::{ptr, GLB_ROOT_CLK_Type};
+
+    pub fn GLB_Get_Root_CLK_Sel() -> GLB_ROOT_CLK_Type {
+        root_clk_sel(unsafe { ptr() }.clk_cfg0.read().bits())
+    }
+
+    pub fn GLB_Get_HCLK_Div() -> u8 {
+        hclk_div(unsafe { ptr() }.clk_cfg0.read().bits())
+    }
+
+    pub fn GLB_Get_BCLK_Div() -> u8 {
+        bclk_div(unsafe { ptr() }.clk_cfg0.read().bits())
+    }
+
+    /// Decode the root clock selection, bits 6:7 of a `clk_cfg0` value
+    pub(crate) fn root_clk_sel(clk_cfg0: u32) -> GLB_ROOT_CLK_Type {
+        match (clk_cfg0 >> 6) & 0b11 {
+            0 => GLB_ROOT_CLK_Type::GLB_ROOT_CLK_RC32M,
+            1 => GLB_ROOT_CLK_Type::GLB_ROOT_CLK_XTAL,
+            _ => GLB_ROOT_CLK_Type::GLB_ROOT_CLK_DLL,
+        }
+    }
+
+    /// Decode the HCLK divider, bits 8:15 of a `clk_cfg0` value
+    pub(crate) fn hclk_div(clk_cfg0: u32) -> u8 {
+        (clk_cfg0 >> 8) as u8
+    }
+
+    /// Decode the BCLK divider, bits 16:23 of a `clk_cfg0` value
+    pub(crate) fn bclk_div(clk_cfg0: u32) -> u8 {
+        (clk_cfg0 >> 16) as u8
+    }
+}
+
+pub fn GLB_Set_UART_CLK(enable: u8, clkSel: HBN_UART_CLK_Type, div: u8) -> BL_Err_Type {
+    if div > 0x7 {
+        return BL_Err_Type::INVALID;
+    }
+    let glb = unsafe { ptr() };
+    // Disable the clock while changing source and divider
+    glb.clk_cfg2.modify(|_, w| w.uart_clk_en().clear_bit());
+    glb.clk_cfg2
+        .modify(|_, w| unsafe { w.uart_clk_div().bits(div) });
+    HBN_Set_UART_CLK_Sel(clkSel);
+    if enable != 0 {
+        glb.clk_cfg2.modify(|_, w| w.uart_clk_en().set_bit());
+    }
     BL_Err_Type::SUCCESS
 }
 
@@ -132,11 +194,57 @@ pub fn GLB_Set_System_CLK_Div(clkDiv: u8, bclkDiv: u8) -> BL_Err_Type {
     }
 }
 
-pub fn GLB_Set_MTimer_CLK(_enable: u8, _clkSel: GLB_MTIMER_CLK_Type, _div: u8) -> BL_Err_Type {
-    //TODO: impl MTimer_CLK
+pub fn GLB_Set_MTimer_CLK(enable: u8, clkSel: GLB_MTIMER_CLK_Type, div: u32) -> BL_Err_Type {
+    if div > 0x1ffff {
+        return BL_Err_Type::INVALID;
+    }
+    let glb = unsafe { ptr() };
+    // Disable the clock while changing source and divider
+    glb.cpu_clk_cfg.modify(|_, w| w.cpu_rtc_en().clear_bit());
+    glb.cpu_clk_cfg.modify(|_, w| unsafe {
+        w.cpu_rtc_sel()
+            .bit(matches!(clkSel, GLB_MTIMER_CLK_Type::GLB_MTIMER_CLK_32K))
+            .cpu_rtc_div()
+            .bits(div)
+    });
+    if enable != 0 {
+        glb.cpu_clk_cfg.modify(|_, w| w.cpu_rtc_en().set_bit());
+    }
     BL_Err_Type::SUCCESS
 }
 
+/// Digital (32K/512K) clock source
+#[repr(C)]
+pub enum GLB_DIG_CLK_Type {
+    /// Select DLL 32M as digital clock source
+    GLB_DIG_CLK_DLL_32M = 0,
+    /// Select XCLK as digital clock source
+    GLB_DIG_CLK_XCLK = 1,
+}
+
+pub fn GLB_Set_DIG_CLK_Sel(clkSel: GLB_DIG_CLK_Type) -> BL_Err_Type {
+    unsafe { ptr() }
+        .dig32k_wakeup_ctrl
+        .modify(|_, w| unsafe { w.dig_clk_src_sel().bits(clkSel as u8) });
+    BL_Err_Type::SUCCESS
+}
+
+/// Configure the digital 32K clock, which is the 32MHz digital clock source / div
+pub fn GLB_Set_DIG_32K_CLK(enable: u8, compensation: u8, div: u16) -> BL_Err_Type {
+    let glb = unsafe { ptr() };
+    glb.dig32k_wakeup_ctrl
+        .modify(|_, w| unsafe { w.dig_32k_div().bits(div) });
+    glb.dig32k_wakeup_ctrl.modify(|_, w| {
+        w.dig_32k_comp()
+            .bit(compensation != 0)
+            .dig_32k_en()
+            .bit(enable != 0)
+    });
+    BL_Err_Type::SUCCESS
+}
+
+/// AHB slave 1 peripherals, the value is the clock gate bit in `cgen_cfg1`
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum BL_AHB_Slave1_Type {
     GLB = 0x00,
     MIX = 0x01,
@@ -194,3 +302,134 @@ pub fn peripheral_clock_gate_all() {
         });
     }
 }
+
+/// Gate (`gate` = 1) or ungate (`gate` = 0) the clock of an AHB slave 1 peripheral.
+///
+/// Returns `INVALID` for peripherals the system needs to keep running, such as
+/// GLB, the flash controller or the always-on domain.
+pub fn GLB_AHB_Slave1_Clock_Gate(gate: u8, slave1: BL_AHB_Slave1_Type) -> BL_Err_Type {
+    match slave1 {
+        BL_AHB_Slave1_Type::GLB
+        | BL_AHB_Slave1_Type::MIX
+        | BL_AHB_Slave1_Type::GPIP
+        | BL_AHB_Slave1_Type::SEC_DBG
+        | BL_AHB_Slave1_Type::SEC
+        | BL_AHB_Slave1_Type::EFUSE
+        | BL_AHB_Slave1_Type::CCI
+        | BL_AHB_Slave1_Type::L1C
+        | BL_AHB_Slave1_Type::S1A_ALL
+        | BL_AHB_Slave1_Type::SFC
+        | BL_AHB_Slave1_Type::PDS_HBN_AON_HBNRAM
+        | BL_AHB_Slave1_Type::RSVD0F
+        | BL_AHB_Slave1_Type::RSVD1B
+        | BL_AHB_Slave1_Type::MAX => return BL_Err_Type::INVALID,
+        _ => {}
+    }
+    let mask = 1 << slave1 as u32;
+    unsafe {
+        ptr().cgen_cfg1.modify(|r, w| {
+            if gate != 0 {
+                w.bits(r.bits() & !mask)
+            } else {
+                w.bits(r.bits() | mask)
+            }
+        });
+    }
+    BL_Err_Type::SUCCESS
+}
+
+/// Ungate the clock of a peripheral. Drivers call this from their constructor.
+pub fn peripheral_clock_enable(peripheral: BL_AHB_Slave1_Type) {
+    GLB_AHB_Slave1_Clock_Gate(0, peripheral);
+}
+
+/// Gate the clock of a peripheral. Drivers call this when they are freed.
+pub fn peripheral_clock_disable(peripheral: BL_AHB_Slave1_Type) {
+    GLB_AHB_Slave1_Clock_Gate(1, peripheral);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::system::romfunc::mock;
+    use core::cell::Cell;
+
+    std::thread_local! {
+        /// `clk_cfg0` value seen by the fake ROM getters
+        static CLK_CFG0: Cell<u32> = const { Cell::new(0) };
+    }
+
+    // Fake ROM getters, decoding CLK_CFG0 with the C SDK field positions
+    extern "C" fn rom_root_clk_sel() -> GLB_ROOT_CLK_Type {
+        match (CLK_CFG0.with(Cell::get) >> 6) & 0x3 {
+            0 => GLB_ROOT_CLK_Type::GLB_ROOT_CLK_RC32M,
+            1 => GLB_ROOT_CLK_Type::GLB_ROOT_CLK_XTAL,
+            _ => GLB_ROOT_CLK_Type::GLB_ROOT_CLK_DLL,
+        }
+    }
+
+    extern "C" fn rom_hclk_div() -> u8 {
+        ((CLK_CFG0.with(Cell::get) >> 8) & 0xff) as u8
+    }
+
+    extern "C" fn rom_bclk_div() -> u8 {
+        ((CLK_CFG0.with(Cell::get) >> 16) & 0xff) as u8
+    }
+
+    fn install_rom_getters() {
+        mock::install(
+            ROM_API_INDEX_e::ROM_API_INDEX_GLB_Get_Root_CLK_Sel,
+            rom_root_clk_sel as *const (),
+        );
+        mock::install(
+            ROM_API_INDEX_e::ROM_API_INDEX_GLB_Get_HCLK_Div,
+            rom_hclk_div as *const (),
+        );
+        mock::install(
+            ROM_API_INDEX_e::ROM_API_INDEX_GLB_Get_BCLK_Div,
+            rom_bclk_div as *const (),
+        );
+    }
+
+    fn call_rom<T>(index: ROM_API_INDEX_e) -> T {
+        unsafe { core::mem::transmute::<*const (), extern "C" fn() -> T>(rom_fn_ptr(index))() }
+    }
+
+    const CLK_CFG0_VALUES: [u32; 5] = [0, 0x0000_0040, 0x0003_0280, 0x00ff_01c0, 0xffff_ffff];
+
+    #[cfg(not(feature = "reg_getters"))]
+    #[test]
+    fn getters_call_their_own_rom_index() {
+        install_rom_getters();
+        // distinct root selection, HCLK and BCLK dividers
+        CLK_CFG0.with(|c| c.set(0x0003_0240));
+
+        assert!(GLB_Get_Root_CLK_Sel() == GLB_ROOT_CLK_Type::GLB_ROOT_CLK_XTAL);
+        assert_eq!(GLB_Get_HCLK_Div(), 2);
+        assert_eq!(GLB_Get_BCLK_Div(), 3);
+    }
+
+    #[test]
+    fn reg_getters_match_rom() {
+        install_rom_getters();
+        for clk_cfg0 in CLK_CFG0_VALUES {
+            CLK_CFG0.with(|c| c.set(clk_cfg0));
+
+            let rom_root: GLB_ROOT_CLK_Type =
+                call_rom(ROM_API_INDEX_e::ROM_API_INDEX_GLB_Get_Root_CLK_Sel);
+            assert!(reg::root_clk_sel(clk_cfg0) == rom_root, "{:#x}", clk_cfg0);
+            assert_eq!(
+                reg::hclk_div(clk_cfg0),
+                call_rom::<u8>(ROM_API_INDEX_e::ROM_API_INDEX_GLB_Get_HCLK_Div),
+                "{:#x}",
+                clk_cfg0
+            );
+            assert_eq!(
+                reg::bclk_div(clk_cfg0),
+                call_rom::<u8>(ROM_API_INDEX_e::ROM_API_INDEX_GLB_Get_BCLK_Div),
+                "{:#x}",
+                clk_cfg0
+            );
+        }
+    }
+}
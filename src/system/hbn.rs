@@ -1,6 +1,9 @@
 #![allow(non_camel_case_types, non_snake_case, clippy::upper_case_acronyms)]
 
-use super::BL_Err_Type;
+use super::{
+    romfunc::{data::ROM_API_INDEX_e, rom_fn_ptr, BL702_Delay_US},
+    BL_Err_Type,
+};
 use crate::pac;
 
 /// Direct register pointer.
@@ -42,19 +45,61 @@ pub enum HBN_XCLK_CLK_Type {
     HBN_XCLK_CLK_XTAL,
 }
 
+// romfunc ((BL_Err_Type(*)(void))ROM_APITABLE[ROM_API_INDEX_HBN_Trim_RC32K])
+/// Apply the RC32K trim code from eFuse, returns `ERROR` if the eFuse holds no valid trim
+pub fn HBN_Trim_RC32K() -> BL_Err_Type {
+    unsafe {
+        core::mem::transmute::<*const (), extern "C" fn() -> BL_Err_Type>(rom_fn_ptr(
+            ROM_API_INDEX_e::ROM_API_INDEX_HBN_Trim_RC32K,
+        ))()
+    }
+}
+
+pub fn HBN_Set_UART_CLK_Sel(clkSel: HBN_UART_CLK_Type) -> BL_Err_Type {
+    unsafe { ptr() }.hbn_glb.modify(|_, w| {
+        w.hbn_uart_clk_sel()
+            .bit(matches!(clkSel, HBN_UART_CLK_Type::HBN_UART_CLK_96M))
+    });
+    BL_Err_Type::SUCCESS
+}
+
 // romfunc
-pub fn HBN_32K_Sel(_clkType: HBN_32K_CLK_Type) -> BL_Err_Type {
-    //TODO: impl HBN_32K_Sel
+pub fn HBN_32K_Sel(clkType: HBN_32K_CLK_Type) -> BL_Err_Type {
+    unsafe { ptr() }
+        .hbn_glb
+        .modify(|_, w| unsafe { w.hbn_f32k_sel().bits(clkType as u8) });
     BL_Err_Type::SUCCESS
 }
 
-pub fn HBN_Set_XCLK_CLK_Sel(_xClk: HBN_XCLK_CLK_Type) -> BL_Err_Type {
-    //TODO: impl XCLK_CLK_Sel
+pub fn HBN_Set_XCLK_CLK_Sel(xClk: HBN_XCLK_CLK_Type) -> BL_Err_Type {
+    unsafe { ptr() }.hbn_glb.modify(|r, w| {
+        // bit 0 of root_clk_sel selects XCLK, bit 1 selects PLL as root
+        let root_clk_sel = match xClk {
+            HBN_XCLK_CLK_Type::HBN_XCLK_CLK_RC32M => r.hbn_root_clk_sel().bits() & !1,
+            HBN_XCLK_CLK_Type::HBN_XCLK_CLK_XTAL => r.hbn_root_clk_sel().bits() | 1,
+        };
+        unsafe { w.hbn_root_clk_sel().bits(root_clk_sel) }
+    });
+    // Give the clock switch a few cycles to settle (GLB_CLK_SET_DUMMY_WAIT)
+    for _ in 0..8 {
+        unsafe { core::arch::asm!("nop") };
+    }
     BL_Err_Type::SUCCESS
 }
 
 // romfunc
 pub fn HBN_Power_On_Xtal_32K() -> BL_Err_Type {
-    //TODO: impl Power_On_Xtal_32K
+    unsafe { ptr() }
+        .xtal32k
+        .modify(|_, w| w.pu_xtal32k().set_bit().pu_xtal32k_buf().set_bit());
+    // Crystal start-up time
+    BL702_Delay_US(1100);
+    BL_Err_Type::SUCCESS
+}
+
+pub fn HBN_Power_Off_Xtal_32K() -> BL_Err_Type {
+    unsafe { ptr() }
+        .xtal32k
+        .modify(|_, w| w.pu_xtal32k().clear_bit().pu_xtal32k_buf().clear_bit());
     BL_Err_Type::SUCCESS
 }
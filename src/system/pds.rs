@@ -1,6 +1,7 @@
 #![allow(non_camel_case_types, non_snake_case, clippy::upper_case_acronyms)]
 
-use crate::system::romfunc::{data::ROM_API_INDEX_e, rom_fn_ptr};
+use super::BL_Err_Type;
+use crate::system::romfunc::{data::ROM_API_INDEX_e, rom_fn_ptr, BL702_Delay_US};
 use bl702_pac;
 
 /// Direct register pointer.
@@ -45,6 +46,171 @@ pub enum PDS_PLL_CLK_Type {
     PDS_PLL_CLK_32M = 8,
 }
 
+// romfunc ((BL_Err_Type(*)(void))ROM_APITABLE[ROM_API_INDEX_PDS_Trim_RC32M])
+/// Apply the RC32M trim code from eFuse, returns `ERROR` if the eFuse holds no valid trim
+pub fn PDS_Trim_RC32M() -> BL_Err_Type {
+    unsafe {
+        core::mem::transmute::<*const (), extern "C" fn() -> BL_Err_Type>(rom_fn_ptr(
+            ROM_API_INDEX_e::ROM_API_INDEX_PDS_Trim_RC32M,
+        ))()
+    }
+}
+
+/// Audio PLL output frequency definition
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum PDS_AUDIO_PLL_Type {
+    /// Audio PLL output clock:11.2896M
+    AUDIO_PLL_11289600_HZ,
+    /// Audio PLL output clock:12.288M
+    AUDIO_PLL_12288000_HZ,
+    /// Audio PLL output clock:22.5792M
+    AUDIO_PLL_22579200_HZ,
+    /// Audio PLL output clock:24.576M
+    AUDIO_PLL_24576000_HZ,
+}
+
+impl PDS_AUDIO_PLL_Type {
+    /// Nominal output frequency in Hz
+    pub const fn freq(self) -> u32 {
+        match self {
+            PDS_AUDIO_PLL_Type::AUDIO_PLL_11289600_HZ => 11_289_600,
+            PDS_AUDIO_PLL_Type::AUDIO_PLL_12288000_HZ => 12_288_000,
+            PDS_AUDIO_PLL_Type::AUDIO_PLL_22579200_HZ => 22_579_200,
+            PDS_AUDIO_PLL_Type::AUDIO_PLL_24576000_HZ => 24_576_000,
+        }
+    }
+
+    /// Post divider that keeps the VCO in its 400-450MHz range
+    const fn postdiv(self) -> u8 {
+        match self {
+            PDS_AUDIO_PLL_Type::AUDIO_PLL_11289600_HZ
+            | PDS_AUDIO_PLL_Type::AUDIO_PLL_12288000_HZ => 36,
+            PDS_AUDIO_PLL_Type::AUDIO_PLL_22579200_HZ
+            | PDS_AUDIO_PLL_Type::AUDIO_PLL_24576000_HZ => 18,
+        }
+    }
+
+    /// Sigma-delta modulator input giving this output frequency
+    const fn sdmin(self) -> u32 {
+        let fref = (32_000_000 / AUDIO_PLL_REFDIV * AUDIO_PLL_FB_MUL) as u64;
+        let vco = self.freq() as u64 * self.postdiv() as u64;
+        (((vco << AUDIO_PLL_SDM_FRAC_BITS) + fref / 2) / fref) as u32
+    }
+}
+
+/// Reference clock divider used for the audio PLL, 32MHz / 2 = 16MHz
+const AUDIO_PLL_REFDIV: u32 = 2;
+/// The feedback path multiplies the reference by 4
+const AUDIO_PLL_FB_MUL: u32 = 4;
+/// Number of fractional bits in clkpll_sdmin
+const AUDIO_PLL_SDM_FRAC_BITS: u32 = 19;
+
+/// Power off the audio PLL
+pub fn PDS_Power_Off_Audio_PLL() {
+    unsafe { ptr() }.pu_rst_clkpll.modify(|_, w| {
+        w.pu_clkpll()
+            .clear_bit()
+            .clkpll_pu_cp()
+            .clear_bit()
+            .clkpll_pu_pfd()
+            .clear_bit()
+            .clkpll_pu_fbdv()
+            .clear_bit()
+            .clkpll_pu_postdiv()
+            .clear_bit()
+    });
+    unsafe { ptr() }
+        .pu_rst_clkpll
+        .modify(|_, w| w.pu_clkpll_sfreg().clear_bit());
+}
+
+/// Configure the audio PLL for the given output frequency and power it on.
+///
+/// The PLL is referenced to the 32MHz crystal, or RC32M if `xtal` is `PDS_PLL_XTAL_RC32M`.
+pub fn PDS_Set_Audio_PLL_Freq(xtal: PDS_PLL_XTAL_Type, audioPLLFreq: PDS_AUDIO_PLL_Type) {
+    let pds = unsafe { ptr() };
+    let postdiv = audioPLLFreq.postdiv();
+    let sdmin = audioPLLFreq.sdmin();
+
+    PDS_Power_Off_Audio_PLL();
+
+    let use_rc32m = matches!(xtal, PDS_PLL_XTAL_Type::PDS_PLL_XTAL_RC32M);
+    pds.clkpll_top_ctrl.modify(|_, w| unsafe {
+        w.clkpll_refclk_sel()
+            .bit(!use_rc32m)
+            .clkpll_xtal_rc32m_sel()
+            .bit(use_rc32m)
+            .clkpll_refdiv_ratio()
+            .bits(AUDIO_PLL_REFDIV as u8)
+            .clkpll_postdiv()
+            .bits(postdiv)
+    });
+    pds.clkpll_sdm
+        .modify(|_, w| unsafe { w.clkpll_sdmin().bits(sdmin) });
+    pds.clkpll_fbdv
+        .modify(|_, w| unsafe { w.clkpll_sel_fb_clk().bits(1) });
+
+    // Enable the regulator first, then the PLL itself
+    pds.pu_rst_clkpll
+        .modify(|_, w| w.pu_clkpll_sfreg().set_bit());
+    BL702_Delay_US(5);
+    pds.pu_rst_clkpll.modify(|_, w| {
+        w.pu_clkpll()
+            .set_bit()
+            .clkpll_pu_cp()
+            .set_bit()
+            .clkpll_pu_pfd()
+            .set_bit()
+            .clkpll_pu_fbdv()
+            .set_bit()
+            .clkpll_pu_postdiv()
+            .set_bit()
+    });
+    BL702_Delay_US(5);
+
+    // Reset the sigma-delta modulator and feedback divider so the new settings take effect
+    pds.pu_rst_clkpll
+        .modify(|_, w| w.clkpll_sdm_reset().set_bit());
+    BL702_Delay_US(1);
+    pds.pu_rst_clkpll
+        .modify(|_, w| w.clkpll_reset_fbdv().set_bit());
+    BL702_Delay_US(2);
+    pds.pu_rst_clkpll
+        .modify(|_, w| w.clkpll_reset_fbdv().clear_bit());
+    BL702_Delay_US(1);
+    pds.pu_rst_clkpll
+        .modify(|_, w| w.clkpll_sdm_reset().clear_bit());
+}
+
+/// Check if the audio PLL is powered on and already set up by
+/// [`PDS_Set_Audio_PLL_Freq`] with the same reference and output frequency
+pub fn PDS_Audio_PLL_Is_Set(xtal: &PDS_PLL_XTAL_Type, audioPLLFreq: PDS_AUDIO_PLL_Type) -> bool {
+    let pds = unsafe { ptr() };
+    if pds.pu_rst_clkpll.read().pu_clkpll().bit_is_clear() {
+        return false;
+    }
+    let use_rc32m = matches!(xtal, PDS_PLL_XTAL_Type::PDS_PLL_XTAL_RC32M);
+    let top_ctrl = pds.clkpll_top_ctrl.read();
+    top_ctrl.clkpll_xtal_rc32m_sel().bit() == use_rc32m
+        && top_ctrl.clkpll_refdiv_ratio().bits() == AUDIO_PLL_REFDIV as u8
+        && top_ctrl.clkpll_postdiv().bits() == audioPLLFreq.postdiv()
+        && pds.clkpll_sdm.read().clkpll_sdmin().bits() == audioPLLFreq.sdmin()
+}
+
+/// Read back the audio PLL output frequency, 0 if it is powered off
+pub fn PDS_Get_Audio_PLL_Freq() -> u32 {
+    let pds = unsafe { ptr() };
+    if pds.pu_rst_clkpll.read().pu_clkpll().bit_is_clear() {
+        return 0;
+    }
+    let top_ctrl = pds.clkpll_top_ctrl.read();
+    let refdiv = (top_ctrl.clkpll_refdiv_ratio().bits() as u64).max(1);
+    let postdiv = (top_ctrl.clkpll_postdiv().bits() as u64).max(1);
+    let sdmin = pds.clkpll_sdm.read().clkpll_sdmin().bits() as u64;
+    let fref = 32_000_000 / refdiv * AUDIO_PLL_FB_MUL as u64;
+    ((fref * sdmin) >> AUDIO_PLL_SDM_FRAC_BITS) as u32 / postdiv as u32
+}
+
 #[inline]
 #[allow(dead_code)]
 fn pds_power_on_pll_rom(xtal: PDS_PLL_XTAL_Type) {
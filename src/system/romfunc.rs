@@ -1,5 +1,4 @@
 use self::data::ROM_API_INDEX_e;
-use self::data::ROM_APITABLE_ADDR;
 
 pub mod data;
 
@@ -11,8 +10,52 @@ pub mod data;
 /// The function pointer needs to be transmuted to the correct signature - take care to match the signature correctly
 /// or memory corruption or other UB could occur
 pub unsafe fn rom_fn_ptr(func: ROM_API_INDEX_e) -> *const () {
-    let rom_function_table_base = ROM_APITABLE_ADDR as *mut usize;
-    let func_entry = rom_function_table_base.wrapping_add(func as usize);
-    let func_addr = func_entry.read_volatile();
-    func_addr as *const ()
+    // Host tests have no ROM, look the function up in the fake table instead
+    #[cfg(test)]
+    return mock::lookup(func as usize);
+
+    #[cfg(not(test))]
+    {
+        let rom_function_table_base = data::ROM_APITABLE_ADDR as *mut usize;
+        let func_entry = rom_function_table_base.wrapping_add(func as usize);
+        let func_addr = func_entry.read_volatile();
+        func_addr as *const ()
+    }
+}
+
+// romfunc ((void (*)(uint32_t cnt))ROM_APITABLE[ROM_API_INDEX_BL702_Delay_US])
+#[allow(non_snake_case)]
+pub fn BL702_Delay_US(cnt: u32) {
+    unsafe {
+        core::mem::transmute::<*const (), extern "C" fn(u32)>(rom_fn_ptr(
+            ROM_API_INDEX_e::ROM_API_INDEX_BL702_Delay_US,
+        ))(cnt)
+    }
+}
+
+/// Per-thread fake ROM function table for host tests.
+///
+/// Tests install a function at each index they expect to be called,
+/// looking up any other index panics.
+#[cfg(test)]
+pub(crate) mod mock {
+    use super::data::ROM_API_INDEX_e;
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+
+    std::thread_local! {
+        static TABLE: RefCell<HashMap<usize, usize>> = RefCell::new(HashMap::new());
+    }
+
+    /// Install `func` as the ROM function at `index`
+    pub fn install(index: ROM_API_INDEX_e, func: *const ()) {
+        TABLE.with(|table| table.borrow_mut().insert(index as usize, func as usize));
+    }
+
+    pub(super) fn lookup(index: usize) -> *const () {
+        TABLE.with(|table| match table.borrow().get(&index) {
+            Some(&func) => func as *const (),
+            None => panic!("unexpected call to ROM function {}", index),
+        })
+    }
 }
@@ -1,10 +1,13 @@
 //! UART driver
 //!
-//! Only supports UART0. Only supports 2MBaud.
+//! Supports UART0 (`pac::UART`) and UART1 ([`UART1`]).
 use crate::clock::Clocks;
 use crate::pac;
+use crate::system::glb;
 
 use core::fmt;
+use core::marker::PhantomData;
+use core::ops::Deref;
 use embedded_hal_alpha::serial::nb::Read as ReadOne;
 use embedded_hal_alpha::serial::nb::Write as WriteOne;
 use embedded_time::rate::{Baud, Extensions};
@@ -17,16 +20,58 @@ use core::convert::Infallible;
 #[derive(Debug)]
 #[non_exhaustive]
 pub enum Error {
-    /// Framing error
+    /// Framing error (not detected by BL702 hardware)
     Framing,
     /// Noise error
     Noise,
     /// RX buffer overrun
     Overrun,
-    /// Parity check error
+    /// RX buffer underrun
+    Underrun,
+    /// Parity check error; the received data in the RX FIFO is discarded
     Parity,
 }
 
+/// Serial configuration error
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ConfigError {
+    /// The requested baudrate can't be generated from the UART clock within tolerance
+    BaudrateOutOfRange,
+    /// The LIN break length must be between 8 and 15 bit times
+    BreakLengthOutOfRange,
+    /// RS-485 mode needs an RTS pin to drive the transceiver DE input
+    MissingRtsPin,
+    /// The stop bits can't be used with the selected word length
+    UnsupportedStopBits,
+    /// LIN mode needs 8 data bits, no parity and LSB-first bit order
+    UnsupportedLinFormat,
+    /// The RX de-glitch width must be between 0 and 15 cycles
+    DeglitchOutOfRange,
+}
+
+/// Maximum deviation from the requested baudrate, in percent
+const BAUDRATE_TOLERANCE_PERCENT: u32 = 2;
+
+/// Calculate the bit-period divisor for `baudrate` from `uart_clk`, rounding to the nearest value
+fn baudrate_divisor(uart_clk: u32, baudrate: u32) -> Result<u16, ConfigError> {
+    if baudrate == 0 {
+        return Err(ConfigError::BaudrateOutOfRange);
+    }
+    // work in u64 so neither the rounding nor the tolerance check can overflow
+    let (uart_clk, baudrate) = (u64::from(uart_clk), u64::from(baudrate));
+    let divisor = (uart_clk + baudrate / 2) / baudrate;
+    // bit period registers are 16 bits wide and hold divisor - 1
+    if !(1..=0x1_0000).contains(&divisor) {
+        return Err(ConfigError::BaudrateOutOfRange);
+    }
+    let actual = uart_clk / divisor;
+    if actual.abs_diff(baudrate) * 100 > baudrate * u64::from(BAUDRATE_TOLERANCE_PERCENT) {
+        return Err(ConfigError::BaudrateOutOfRange);
+    }
+    Ok((divisor - 1) as u16)
+}
+
 /// Serial configuration
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub struct Config {
@@ -35,6 +80,11 @@ pub struct Config {
     pub parity: Parity,
     pub stopbits: StopBits,
     pub wordlength: WordLength,
+    pub lin: bool,
+    pub break_bits: u8,
+    pub auto_baudrate: bool,
+    pub rs485: bool,
+    pub rx_deglitch: Option<u8>,
 }
 
 impl Config {
@@ -72,6 +122,97 @@ impl Config {
 
         self
     }
+
+    /// Sets the number of data bits
+    pub fn wordlength(mut self, wordlength: WordLength) -> Self {
+        self.wordlength = wordlength;
+
+        self
+    }
+
+    /// Sets the order of the bits on the wire
+    pub fn order(mut self, order: Order) -> Self {
+        self.order = order;
+
+        self
+    }
+
+    /// Enables the RX de-glitch filter, rejecting pulses up to `cycles` UART clock cycles wide (0 to 15)
+    pub fn rx_deglitch(mut self, cycles: u8) -> Self {
+        self.rx_deglitch = Some(cycles);
+
+        self
+    }
+
+    /// Disables the RX de-glitch filter
+    pub fn rx_deglitch_none(mut self) -> Self {
+        self.rx_deglitch = None;
+
+        self
+    }
+
+    /// Enables LIN mode.
+    ///
+    /// TX sends a break and sync field before each frame,
+    /// RX detects them and raises [`Event::LinSyncError`] if the sync field is not 0x55.
+    ///
+    /// The BL702 has no status or interrupt for a correctly received break, only sync errors
+    /// are reported. A LIN slave should listen for [`Event::RxFifoReady`] (or
+    /// [`Event::RxTimeout`]) and read the header bytes from the FIFO; a header that arrives
+    /// without a `LinSyncError` had a valid break and sync field.
+    pub fn lin(mut self, enable: bool) -> Self {
+        self.lin = enable;
+
+        self
+    }
+
+    /// Sets the length of the LIN break field in bit times (8 to 15)
+    pub fn break_bits(mut self, break_bits: u8) -> Self {
+        self.break_bits = break_bits;
+
+        self
+    }
+
+    /// Enables measurement of the incoming baudrate, see [`Serial::detected_baudrate`]
+    pub fn auto_baudrate(mut self, enable: bool) -> Self {
+        self.auto_baudrate = enable;
+
+        self
+    }
+
+    /// Enables RS-485 mode.
+    ///
+    /// RTS drives the transceiver DE input: it goes high when data is written
+    /// and is released once the transmitter is idle. `fmt::Write` releases it
+    /// at the end of each string, clearing [`Event::TxTransferEnd`] releases it
+    /// if the transmitter has gone idle, and `flush` releases it otherwise.
+    pub fn rs485(mut self, enable: bool) -> Self {
+        self.rs485 = enable;
+
+        self
+    }
+
+    /// Check for combinations of options the hardware can't do
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        // 0.5 stop bits are only supported for 5 bit words
+        if self.stopbits == StopBits::STOP0P5 && self.wordlength != WordLength::Five {
+            return Err(ConfigError::UnsupportedStopBits);
+        }
+        if self.lin
+            && (self.wordlength != WordLength::Eight
+                || self.parity != Parity::ParityNone
+                || self.order != Order::LsbFirst)
+        {
+            return Err(ConfigError::UnsupportedLinFormat);
+        }
+        if !(8..=15).contains(&self.break_bits) {
+            return Err(ConfigError::BreakLengthOutOfRange);
+        }
+        if matches!(self.rx_deglitch, Some(cycles) if cycles > 15) {
+            return Err(ConfigError::DeglitchOutOfRange);
+        }
+        Ok(())
+    }
 }
 
 impl Default for Config {
@@ -82,6 +223,11 @@ impl Default for Config {
             parity: Parity::ParityNone,
             stopbits: StopBits::STOP1,
             wordlength: WordLength::Eight,
+            lin: false,
+            break_bits: 13,
+            auto_baudrate: false,
+            rs485: false,
+            rx_deglitch: None,
         }
     }
 }
@@ -128,7 +274,17 @@ pub enum WordLength {
     Eight,
 }
 
+/// Auto baudrate measurement mode
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum AutoBaudMode {
+    /// Measure the length of the start bit
+    StartBit,
+    /// Measure the bit period of a received 0x55 character (e.g. a LIN sync field)
+    Sync0x55,
+}
+
 /// Interrupt event
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum Event {
     /// UART RX FIFO error interrupt
     RxFifoError,
@@ -146,25 +302,174 @@ pub enum Event {
     RxTransferEnd,
     /// UART TX transfer end interrupt
     TxTransferEnd,
+    /// UART RX LIN sync field error interrupt.
+    /// There is no event for a correctly received break, see [`Config::lin`].
+    LinSyncError,
+}
+
+impl Event {
+    /// Bit for this event in the interrupt status, mask, clear and enable registers
+    fn mask(self) -> u32 {
+        let bit = match self {
+            Event::TxTransferEnd => 0,
+            Event::RxTransferEnd => 1,
+            Event::TxFifoReady => 2,
+            Event::RxFifoReady => 3,
+            Event::RxTimeout => 4,
+            Event::RxParityError => 5,
+            Event::TxFifoError => 6,
+            Event::RxFifoError => 7,
+            Event::LinSyncError => 8,
+        };
+        1 << bit
+    }
+}
+
+/// Depth of the TX and RX FIFOs
+const FIFO_DEPTH: u8 = 32;
+
+/// UART1 peripheral
+///
+/// `bl702-pac` only describes UART0 (`pac::UART`),
+/// UART1 uses the same register layout at a different base address.
+pub struct UART1 {
+    _marker: PhantomData<*const ()>,
+}
+
+unsafe impl Send for UART1 {}
+
+static mut UART1_TAKEN: bool = false;
+
+impl UART1 {
+    /// Pointer to the register block
+    pub const PTR: *const pac::uart::RegisterBlock = 0x4000_a100 as *const _;
+
+    /// Return the pointer to the register block
+    #[inline(always)]
+    pub const fn ptr() -> *const pac::uart::RegisterBlock {
+        Self::PTR
+    }
+
+    /// Returns the UART1 peripheral, but only the first time it is called
+    pub fn take() -> Option<Self> {
+        riscv::interrupt::free(|| unsafe {
+            if UART1_TAKEN {
+                None
+            } else {
+                UART1_TAKEN = true;
+                Some(Self::steal())
+            }
+        })
+    }
+
+    /// Unchecked version of `UART1::take`
+    ///
+    /// # Safety
+    /// Only one instance of UART1 may be in use at a time
+    pub unsafe fn steal() -> Self {
+        UART1 {
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl Deref for UART1 {
+    type Target = pac::uart::RegisterBlock;
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        unsafe { &*Self::PTR }
+    }
+}
+
+/// UART peripheral instance - DO NOT IMPLEMENT THIS TRAIT
+///
+/// # Safety
+/// DO NOT IMPLEMENT THIS TRAIT
+pub unsafe trait Instance: Deref<Target = pac::uart::RegisterBlock> {
+    #[doc(hidden)]
+    fn ptr() -> *const pac::uart::RegisterBlock;
+    #[doc(hidden)]
+    const CLOCK: glb::BL_AHB_Slave1_Type;
+}
+
+unsafe impl Instance for pac::UART {
+    fn ptr() -> *const pac::uart::RegisterBlock {
+        pac::UART::PTR
+    }
+
+    const CLOCK: glb::BL_AHB_Slave1_Type = glb::BL_AHB_Slave1_Type::UART0;
+}
+
+unsafe impl Instance for UART1 {
+    fn ptr() -> *const pac::uart::RegisterBlock {
+        UART1::PTR
+    }
+
+    const CLOCK: glb::BL_AHB_Slave1_Type = glb::BL_AHB_Slave1_Type::UART1;
 }
 
 /// Serial abstraction
 pub struct Serial<UART, PINS> {
     uart: UART,
     pins: PINS,
+    baudrate: Baud,
+    rs485: bool,
 }
 
 impl<PINS> Serial<pac::UART, PINS>
 where
     PINS: Pins<pac::UART>,
 {
-    // todo: there is UART0 and UART1
-    // todo: use clocks
-    pub fn uart0(uart: pac::UART, config: Config, pins: PINS, _clocks: Clocks) -> Self {
+    /// Configure UART0 with the given config and pins.
+    ///
+    /// Returns an error if `config.baudrate` can't be derived from the UART clock
+    pub fn uart0(
+        uart: pac::UART,
+        config: Config,
+        pins: PINS,
+        clocks: Clocks,
+    ) -> Result<Self, ConfigError> {
+        Self::configure(uart, config, pins, clocks)
+    }
+}
+
+impl<PINS> Serial<UART1, PINS>
+where
+    PINS: Pins<UART1>,
+{
+    /// Configure UART1 with the given config and pins.
+    ///
+    /// Returns an error if `config.baudrate` can't be derived from the UART clock
+    pub fn uart1(
+        uart: UART1,
+        config: Config,
+        pins: PINS,
+        clocks: Clocks,
+    ) -> Result<Self, ConfigError> {
+        Self::configure(uart, config, pins, clocks)
+    }
+}
+
+impl<UART, PINS> Serial<UART, PINS>
+where
+    UART: Instance,
+    PINS: Pins<UART>,
+{
+    fn configure(
+        uart: UART,
+        config: Config,
+        pins: PINS,
+        clocks: Clocks,
+    ) -> Result<Self, ConfigError> {
+        config.validate()?;
+        if config.rs485 && !PINS::HAS_RTS {
+            return Err(ConfigError::MissingRtsPin);
+        }
+
         // Initialize clocks and baudrate
-        // let uart_clk = clocks.uart_clk();
-        // let mut baud = config.baudrate.0;
-        let divisor = 48; // bring uart down from 96MHz to 2Mhz
+        let bit_prd = baudrate_divisor(clocks.uart_clk().0, config.baudrate.0)?;
+        glb::peripheral_clock_enable(UART::CLOCK);
 
         // Disable uart first
         uart.utx_config.modify(|_, w| w.cr_utx_en().clear_bit());
@@ -172,9 +477,9 @@ where
 
         uart.uart_bit_prd.write(|w| unsafe {
             w.cr_urx_bit_prd()
-                .bits(divisor - 1)
+                .bits(bit_prd)
                 .cr_utx_bit_prd()
-                .bits(divisor - 1)
+                .bits(bit_prd)
         });
 
         // Bit inverse configuration; MsbFirst => 1, LsbFirst => 0
@@ -211,7 +516,9 @@ where
             w.cr_utx_bit_cnt_d().bits(data_bits_cfg);
             w.cr_utx_bit_cnt_p().bits(stop_bits_cfg);
             w.cr_utx_frm_en().set_bit(); // [!] freerun on // todo
-            w.cr_utx_cts_en().bit(false); // force CTS off for testing
+            w.cr_utx_cts_en().bit(PINS::HAS_CTS); // only transmit while CTS is asserted
+            w.cr_utx_lin_en().bit(config.lin);
+            w.cr_utx_bit_cnt_b().bits(config.break_bits - 8); // hardware adds 8 bit times
             w
         });
         uart.utx_config
@@ -222,67 +529,327 @@ where
             w.cr_urx_prt_en().bit(parity_enable);
             w.cr_urx_prt_sel().bit(parity_type);
             w.cr_urx_bit_cnt_d().bits(data_bits_cfg);
-            w.cr_urx_deg_en().clear_bit();
-            // no rx input de-glitch // todo
-            // .cr_urx_rts_sw_mode()
-            // .clear_bit() // no RTS // todo
+            w.cr_urx_deg_en().bit(config.rx_deglitch.is_some());
+            w.cr_urx_deg_cnt().bits(config.rx_deglitch.unwrap_or(0));
+            w.cr_urx_lin_en().bit(config.lin);
+            w.cr_urx_abr_en().bit(config.auto_baudrate);
             w.cr_urx_en().bit(PINS::HAS_RX);
             w
         });
 
-        uart.urx_config
-            .modify(|_, w| unsafe { w.cr_urx_deg_cnt().bits(15) });
+        if config.rs485 {
+            // RTS is the driver enable, keep it released until we transmit
+            uart.uart_sw_mode.modify(|_, w| {
+                w.cr_urx_rts_sw_val().clear_bit();
+                w.cr_urx_rts_sw_mode().set_bit()
+            });
+        } else {
+            // Let the hardware drive RTS from the RX FIFO level
+            uart.uart_sw_mode
+                .modify(|_, w| w.cr_urx_rts_sw_mode().clear_bit());
+        }
+
+        Ok(Serial {
+            uart,
+            pins,
+            baudrate: config.baudrate,
+            rs485: config.rs485,
+        })
+    }
+
+    /// Take manual control of RTS and assert it (drive low), signalling that we can receive
+    pub fn assert_rts(&mut self) {
+        self.uart.uart_sw_mode.modify(|_, w| {
+            w.cr_urx_rts_sw_val().clear_bit();
+            w.cr_urx_rts_sw_mode().set_bit()
+        });
+    }
+
+    /// Take manual control of RTS and deassert it (drive high), asking the remote to stop sending
+    pub fn deassert_rts(&mut self) {
+        self.uart.uart_sw_mode.modify(|_, w| {
+            w.cr_urx_rts_sw_val().set_bit();
+            w.cr_urx_rts_sw_mode().set_bit()
+        });
+    }
+
+    /// Return RTS to hardware flow control
+    pub fn auto_rts(&mut self) {
+        self.uart
+            .uart_sw_mode
+            .modify(|_, w| w.cr_urx_rts_sw_mode().clear_bit());
+    }
+
+    /// Start sending a break by holding TX low until [`Serial::stop_break`] is called
+    pub fn start_break(&mut self) {
+        self.uart.uart_sw_mode.modify(|_, w| {
+            w.cr_utx_txd_sw_val().clear_bit();
+            w.cr_utx_txd_sw_mode().set_bit()
+        });
+    }
+
+    /// Stop sending a break and return TX to the transmitter
+    pub fn stop_break(&mut self) {
+        self.uart
+            .uart_sw_mode
+            .modify(|_, w| w.cr_utx_txd_sw_mode().clear_bit());
+    }
+
+    /// Baudrate measured by the receiver, if auto baudrate detection is enabled and has seen traffic
+    pub fn detected_baudrate(&self, mode: AutoBaudMode, clocks: Clocks) -> Option<Baud> {
+        let abr_prd = self.uart.sts_urx_abr_prd.read();
+        let bit_prd = match mode {
+            AutoBaudMode::StartBit => abr_prd.sts_urx_abr_prd_start().bits(),
+            AutoBaudMode::Sync0x55 => abr_prd.sts_urx_abr_prd_0x55().bits(),
+        };
+        if bit_prd == 0 {
+            None
+        } else {
+            Some(Baud(clocks.uart_clk().0 / (bit_prd as u32 + 1)))
+        }
+    }
+
+    /// Start listening for an interrupt event
+    pub fn listen(&mut self, event: Event) {
+        let mask = event.mask();
+        self.uart
+            .uart_int_en
+            .modify(|r, w| unsafe { w.bits(r.bits() | mask) });
+        self.uart
+            .uart_int_mask
+            .modify(|r, w| unsafe { w.bits(r.bits() & !mask) });
+    }
+
+    /// Stop listening for an interrupt event
+    pub fn unlisten(&mut self, event: Event) {
+        let mask = event.mask();
+        self.uart
+            .uart_int_mask
+            .modify(|r, w| unsafe { w.bits(r.bits() | mask) });
+    }
+
+    /// Check if an interrupt event is pending
+    pub fn is_pending(&self, event: Event) -> bool {
+        self.uart.uart_int_sts.read().bits() & event.mask() != 0
+    }
+
+    /// Clear a pending interrupt event
+    ///
+    /// `RxFifoReady` and `TxFifoReady` follow the FIFO level and are cleared by
+    /// reading or writing the FIFO. Clearing `RxFifoError` or `TxFifoError`
+    /// flushes the corresponding FIFO. Clearing `TxTransferEnd` in RS-485 mode
+    /// also releases DE if the transmitter is idle.
+    pub fn clear(&mut self, event: Event) {
+        match event {
+            Event::RxFifoReady | Event::TxFifoReady => {}
+            Event::RxFifoError => self
+                .uart
+                .uart_fifo_config_0
+                .modify(|_, w| w.rx_fifo_clr().set_bit()),
+            Event::TxFifoError => self
+                .uart
+                .uart_fifo_config_0
+                .modify(|_, w| w.tx_fifo_clr().set_bit()),
+            Event::TxTransferEnd => {
+                self.uart
+                    .uart_int_clear
+                    .write(|w| unsafe { w.bits(event.mask()) });
+                // Release the RS-485 driver if the transmitter has gone idle
+                let _ = flush(&self.uart, self.rs485);
+            }
+            _ => self
+                .uart
+                .uart_int_clear
+                .write(|w| unsafe { w.bits(event.mask()) }),
+        }
+    }
+
+    /// Set the RX FIFO threshold, `RxFifoReady` fires while more than `threshold` bytes are queued
+    pub fn set_rx_fifo_threshold(&mut self, threshold: u8) {
+        assert!(threshold < FIFO_DEPTH, "RX FIFO threshold out of range");
+        self.uart
+            .uart_fifo_config_1
+            .modify(|_, w| unsafe { w.rx_fifo_th().bits(threshold) });
+    }
+
+    /// Set the TX FIFO threshold, `TxFifoReady` fires while more than `threshold` bytes are free
+    pub fn set_tx_fifo_threshold(&mut self, threshold: u8) {
+        assert!(threshold < FIFO_DEPTH, "TX FIFO threshold out of range");
+        self.uart
+            .uart_fifo_config_1
+            .modify(|_, w| unsafe { w.tx_fifo_th().bits(threshold) });
+    }
+
+    /// Set the RX timeout, `RxTimeout` fires after the line has been idle for `bit_times` bits
+    pub fn set_rx_timeout(&mut self, bit_times: u8) {
+        self.uart
+            .urx_rto_timer
+            .modify(|_, w| unsafe { w.cr_urx_rto_value().bits(bit_times.max(1) - 1) });
+    }
 
-        Serial { uart, pins }
+    /// Recompute the bit period for the configured baudrate after a clock change.
+    ///
+    /// Blocks until any queued data has been transmitted. On error the old bit period is kept.
+    pub fn reclock(&mut self, clocks: Clocks) -> Result<(), ConfigError> {
+        reclock(&self.uart, self.baudrate, self.rs485, clocks)
     }
 
-    pub fn free(self) -> (pac::UART, PINS) {
-        // todo!
+    /// Disable the UART and gate its clock, returning the peripheral and pins.
+    ///
+    /// Data still queued in the TX FIFO is discarded, call `flush` first to wait
+    /// for it to be sent. The pins are returned still muxed to the UART function,
+    /// convert them to another mode before reusing them.
+    pub fn free(self) -> (UART, PINS) {
+        self.uart
+            .utx_config
+            .modify(|_, w| w.cr_utx_en().clear_bit());
+        self.uart
+            .urx_config
+            .modify(|_, w| w.cr_urx_en().clear_bit());
+        self.uart
+            .uart_fifo_config_0
+            .modify(|_, w| w.tx_fifo_clr().set_bit().rx_fifo_clr().set_bit());
+        // Mask all interrupts and hand TXD/RTS back to the hardware
+        self.uart.uart_int_mask.write(|w| unsafe { w.bits(0x1ff) });
+        self.uart.uart_sw_mode.reset();
+
+        glb::peripheral_clock_disable(UART::CLOCK);
+
         (self.uart, self.pins)
     }
+
+    /// Split the serial port into independent transmitter and receiver halves.
+    ///
+    /// The pins are handed back so they can be passed to [`Serial::join`] later.
+    pub fn split(self) -> (Tx<UART>, Rx<UART>, PINS) {
+        (
+            Tx {
+                uart: self.uart,
+                baudrate: self.baudrate,
+                rs485: self.rs485,
+            },
+            Rx { _uart: PhantomData },
+            self.pins,
+        )
+    }
+
+    /// Reassemble a serial port from the halves returned by [`Serial::split`]
+    pub fn join(tx: Tx<UART>, _rx: Rx<UART>, pins: PINS) -> Self {
+        Serial {
+            uart: tx.uart,
+            pins,
+            baudrate: tx.baudrate,
+            rs485: tx.rs485,
+        }
+    }
+}
+
+fn reclock(
+    uart: &pac::uart::RegisterBlock,
+    baudrate: Baud,
+    rs485: bool,
+    clocks: Clocks,
+) -> Result<(), ConfigError> {
+    let bit_prd = baudrate_divisor(clocks.uart_clk().0, baudrate.0)?;
+    let _ = block!(flush(uart, rs485));
+
+    uart.uart_bit_prd.write(|w| unsafe {
+        w.cr_urx_bit_prd()
+            .bits(bit_prd)
+            .cr_utx_bit_prd()
+            .bits(bit_prd)
+    });
+    Ok(())
 }
 
-impl<PINS> embedded_hal_alpha::serial::nb::Write<u8> for Serial<pac::UART, PINS> {
+fn write_byte(uart: &pac::uart::RegisterBlock, word: u8, rs485: bool) -> nb::Result<(), Error> {
+    // If there's no room to write a byte or more to the FIFO, return WouldBlock
+    if uart.uart_fifo_config_1.read().tx_fifo_cnt().bits() == 0 {
+        Err(nb::Error::WouldBlock)
+    } else {
+        if rs485 {
+            // Enable the RS-485 driver before the byte hits the wire
+            uart.uart_sw_mode
+                .modify(|_, w| w.cr_urx_rts_sw_val().set_bit());
+        }
+        uart.uart_fifo_wdata
+            .write(|w| unsafe { w.bits(word as u32) });
+        Ok(())
+    }
+}
+
+fn flush(uart: &pac::uart::RegisterBlock, rs485: bool) -> nb::Result<(), Error> {
+    // If we're still transmitting or have data in our 32 byte FIFO, return WouldBlock
+    if uart.uart_fifo_config_1.read().tx_fifo_cnt().bits() != FIFO_DEPTH
+        || uart.uart_status.read().sts_utx_bus_busy().bit_is_set()
+    {
+        Err(nb::Error::WouldBlock)
+    } else {
+        if rs485 {
+            // Transmitter is idle, release the bus
+            uart.uart_sw_mode
+                .modify(|_, w| w.cr_urx_rts_sw_val().clear_bit());
+        }
+        Ok(())
+    }
+}
+
+/// Check for and clear RX line errors.
+///
+/// FIFO overflow and underflow can only be cleared by flushing the RX FIFO,
+/// so any bytes still queued are discarded. The RX FIFO is flushed on a parity
+/// error too, as the FIFO doesn't record which queued byte was corrupted.
+fn check_rx_errors(uart: &pac::uart::RegisterBlock) -> Result<(), Error> {
+    let fifo_config_0 = uart.uart_fifo_config_0.read();
+    if fifo_config_0.rx_fifo_overflow().bit_is_set() {
+        uart.uart_fifo_config_0
+            .modify(|_, w| w.rx_fifo_clr().set_bit());
+        Err(Error::Overrun)
+    } else if fifo_config_0.rx_fifo_underflow().bit_is_set() {
+        uart.uart_fifo_config_0
+            .modify(|_, w| w.rx_fifo_clr().set_bit());
+        Err(Error::Underrun)
+    } else if uart.uart_int_sts.read().urx_pce_int().bit_is_set() {
+        uart.uart_fifo_config_0
+            .modify(|_, w| w.rx_fifo_clr().set_bit());
+        uart.uart_int_clear.write(|w| w.cr_urx_pce_clr().set_bit());
+        Err(Error::Parity)
+    } else {
+        Ok(())
+    }
+}
+
+fn read_byte(uart: &pac::uart::RegisterBlock) -> nb::Result<u8, Error> {
+    check_rx_errors(uart)?;
+    if uart.uart_fifo_config_1.read().rx_fifo_cnt().bits() == 0 {
+        Err(nb::Error::WouldBlock)
+    } else {
+        let ans = uart.uart_fifo_rdata.read().bits();
+        Ok((ans & 0xff) as u8)
+    }
+}
+
+impl<UART: Instance, PINS> embedded_hal_alpha::serial::nb::Write<u8> for Serial<UART, PINS> {
     type Error = Error;
 
     fn write(&mut self, word: u8) -> nb::Result<(), Self::Error> {
-        // If there's no room to write a byte or more to the FIFO, return WouldBlock
-        if self.uart.uart_fifo_config_1.read().tx_fifo_cnt().bits() == 0 {
-            Err(nb::Error::WouldBlock)
-        } else {
-            self.uart
-                .uart_fifo_wdata
-                .write(|w| unsafe { w.bits(word as u32) });
-            Ok(())
-        }
+        write_byte(&self.uart, word, self.rs485)
     }
 
     fn flush(&mut self) -> nb::Result<(), Self::Error> {
-        // If we're still transmitting or have data in our 32 byte FIFO, return WouldBlock
-        if self.uart.uart_fifo_config_1.read().tx_fifo_cnt().bits() != 32
-            || self.uart.uart_status.read().sts_utx_bus_busy().bit_is_set()
-        {
-            Err(nb::Error::WouldBlock)
-        } else {
-            Ok(())
-        }
+        flush(&self.uart, self.rs485)
     }
 }
 
-impl<PINS> embedded_hal_alpha::serial::nb::Read<u8> for Serial<pac::UART, PINS> {
+impl<UART: Instance, PINS> embedded_hal_alpha::serial::nb::Read<u8> for Serial<UART, PINS> {
     type Error = Error;
 
     fn read(&mut self) -> nb::Result<u8, Self::Error> {
-        if self.uart.uart_fifo_config_1.read().rx_fifo_cnt().bits() == 0 {
-            Err(nb::Error::WouldBlock)
-        } else {
-            let ans = self.uart.uart_fifo_rdata.read().bits();
-            Ok((ans & 0xff) as u8)
-        }
+        read_byte(&self.uart)
     }
 }
 
-impl<PINS> embedded_hal::serial::Write<u8> for Serial<pac::UART, PINS> {
+impl<UART: Instance, PINS> embedded_hal::serial::Write<u8> for Serial<UART, PINS> {
     type Error = Error;
 
     fn write(&mut self, word: u8) -> nb::Result<(), Self::Error> {
@@ -294,7 +861,7 @@ impl<PINS> embedded_hal::serial::Write<u8> for Serial<pac::UART, PINS> {
     }
 }
 
-impl<PINS> embedded_hal::serial::Read<u8> for Serial<pac::UART, PINS> {
+impl<UART: Instance, PINS> embedded_hal::serial::Read<u8> for Serial<UART, PINS> {
     type Error = Error;
 
     fn read(&mut self) -> nb::Result<u8, Self::Error> {
@@ -310,7 +877,91 @@ where
         s.as_bytes()
             .iter()
             .try_for_each(|c| block!(self.write(*c)))
-            .map_err(|_| fmt::Error)
+            .map_err(|_| fmt::Error)?;
+        if self.rs485 {
+            // Release the RS-485 driver once the string is on the wire
+            block!(self.flush()).map_err(|_| fmt::Error)?;
+        }
+        Ok(())
+    }
+}
+
+/// Serial transmitter half
+pub struct Tx<UART> {
+    uart: UART,
+    baudrate: Baud,
+    rs485: bool,
+}
+
+impl<UART: Instance> Tx<UART> {
+    /// Recompute the bit period for the configured baudrate after a clock change.
+    ///
+    /// The receiver half shares the bit period register and is updated too.
+    /// Blocks until any queued data has been transmitted. On error the old bit period is kept.
+    pub fn reclock(&mut self, clocks: Clocks) -> Result<(), ConfigError> {
+        reclock(&self.uart, self.baudrate, self.rs485, clocks)
+    }
+}
+
+/// Serial receiver half
+pub struct Rx<UART> {
+    _uart: PhantomData<UART>,
+}
+
+impl<UART: Instance> embedded_hal_alpha::serial::nb::Write<u8> for Tx<UART> {
+    type Error = Error;
+
+    fn write(&mut self, word: u8) -> nb::Result<(), Self::Error> {
+        write_byte(&self.uart, word, self.rs485)
+    }
+
+    fn flush(&mut self) -> nb::Result<(), Self::Error> {
+        flush(&self.uart, self.rs485)
+    }
+}
+
+impl<UART: Instance> embedded_hal::serial::Write<u8> for Tx<UART> {
+    type Error = Error;
+
+    fn write(&mut self, word: u8) -> nb::Result<(), Self::Error> {
+        WriteOne::write(self, word)
+    }
+
+    fn flush(&mut self) -> nb::Result<(), Self::Error> {
+        WriteOne::flush(self)
+    }
+}
+
+impl<UART> fmt::Write for Tx<UART>
+where
+    Tx<UART>: embedded_hal_alpha::serial::nb::Write<u8>,
+{
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        s.as_bytes()
+            .iter()
+            .try_for_each(|c| block!(self.write(*c)))
+            .map_err(|_| fmt::Error)?;
+        if self.rs485 {
+            // Release the RS-485 driver once the string is on the wire
+            block!(self.flush()).map_err(|_| fmt::Error)?;
+        }
+        Ok(())
+    }
+}
+
+impl<UART: Instance> embedded_hal_alpha::serial::nb::Read<u8> for Rx<UART> {
+    type Error = Error;
+
+    fn read(&mut self) -> nb::Result<u8, Self::Error> {
+        read_byte(unsafe { &*UART::ptr() })
+    }
+}
+
+impl<UART: Instance> embedded_hal::serial::Read<u8> for Rx<UART> {
+    type Error = Error;
+
+    fn read(&mut self) -> nb::Result<u8, Self::Error> {
+        ReadOne::read(self)
     }
 }
 
@@ -344,6 +995,10 @@ macro_rules! impl_uart_pin {
         unsafe impl<PIN: UartPin<$UartSigi>> RxPin<pac::UART> for (PIN, $UartMuxi<Uart0Rx>) {}
         unsafe impl<PIN: UartPin<$UartSigi>> RtsPin<pac::UART> for (PIN, $UartMuxi<Uart0Rts>) {}
         unsafe impl<PIN: UartPin<$UartSigi>> CtsPin<pac::UART> for (PIN, $UartMuxi<Uart0Cts>) {}
+        unsafe impl<PIN: UartPin<$UartSigi>> TxPin<UART1> for (PIN, $UartMuxi<Uart1Tx>) {}
+        unsafe impl<PIN: UartPin<$UartSigi>> RxPin<UART1> for (PIN, $UartMuxi<Uart1Rx>) {}
+        unsafe impl<PIN: UartPin<$UartSigi>> RtsPin<UART1> for (PIN, $UartMuxi<Uart1Rts>) {}
+        unsafe impl<PIN: UartPin<$UartSigi>> CtsPin<UART1> for (PIN, $UartMuxi<Uart1Cts>) {}
         )+
     };
 }
@@ -382,12 +1037,25 @@ where
     const HAS_CTS: bool = false;
 }
 
+unsafe impl<UART, TX, RX, RTS> Pins<UART> for (TX, RX, RTS)
+where
+    TX: TxPin<UART>,
+    RX: RxPin<UART>,
+    RTS: RtsPin<UART>,
+{
+    const HAS_TX: bool = true;
+    const HAS_RX: bool = true;
+    const HAS_RTS: bool = true;
+    const HAS_CTS: bool = false;
+}
+
+/// Supplying RTS and CTS pins enables hardware flow control
 unsafe impl<UART, TX, RX, RTS, CTS> Pins<UART> for (TX, RX, RTS, CTS)
 where
     TX: TxPin<UART>,
     RX: RxPin<UART>,
-    RTS: RxPin<UART>,
-    CTS: RxPin<UART>,
+    RTS: RtsPin<UART>,
+    CTS: CtsPin<UART>,
 {
     const HAS_TX: bool = true;
     const HAS_RX: bool = true;
@@ -413,7 +1081,7 @@ impl LoggerToken {
 #[cfg(feature = "print_serial")]
 impl fmt::Write for LoggerToken {
     fn write_str(&mut self, s: &str) -> fmt::Result {
-        s.as_bytes().iter().for_each(|c| (self.write(*c)));
+        s.as_bytes().iter().for_each(|c| self.write(*c));
         Ok(())
     }
 }
@@ -425,7 +1093,7 @@ use ufmt_write::uWrite;
 impl uWrite for LoggerToken {
     type Error = Infallible;
     fn write_str(&mut self, s: &str) -> Result<(), Self::Error> {
-        s.as_bytes().iter().for_each(|c| (self.write(*c)));
+        s.as_bytes().iter().for_each(|c| self.write(*c));
         Ok(())
     }
 }
@@ -454,7 +1122,7 @@ pub mod serial_logger {
         riscv::interrupt::free(|| {
             // statics not working? avoiding for now
             unsafe {
-                let _ = GLOBAL_SERIAL.insert(LoggerToken {});
+                *core::ptr::addr_of_mut!(GLOBAL_SERIAL) = Some(LoggerToken {});
             }
         })
     }
@@ -484,7 +1152,7 @@ pub mod serial_logger {
     }
 }
 
-#[cfg(feature = "panic_serial")]
+#[cfg(all(feature = "panic_serial", not(test)))]
 #[panic_handler]
 fn panic(info: &core::panic::PanicInfo) -> ! {
     use crate::serial_println;
@@ -494,3 +1162,34 @@ fn panic(info: &core::panic::PanicInfo) -> ! {
     });
     loop {}
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{baudrate_divisor, ConfigError};
+
+    #[test]
+    fn baudrate_divisor_rounds_to_nearest() {
+        assert_eq!(baudrate_divisor(96_000_000, 2_000_000).unwrap(), 47);
+        assert_eq!(baudrate_divisor(96_000_000, 115_200).unwrap(), 832);
+    }
+
+    #[test]
+    fn baudrate_divisor_out_of_range() {
+        assert!(matches!(
+            baudrate_divisor(96_000_000, 150_000_000),
+            Err(ConfigError::BaudrateOutOfRange)
+        ));
+        assert!(matches!(
+            baudrate_divisor(96_000_000, u32::MAX),
+            Err(ConfigError::BaudrateOutOfRange)
+        ));
+        assert!(matches!(
+            baudrate_divisor(96_000_000, 0),
+            Err(ConfigError::BaudrateOutOfRange)
+        ));
+        assert!(matches!(
+            baudrate_divisor(96_000_000, 1_000),
+            Err(ConfigError::BaudrateOutOfRange)
+        ));
+    }
+}